@@ -2,37 +2,6 @@
 
 set -e
 
-function get_input() {
-  local -r year=$(printf "%d" "$1");
-  local -r day=$(printf "%d" "$2");
-
-  local -r scriptdir=$( cd -- "$( dirname -- "${BASH_SOURCE[0]}" )" &> /dev/null && pwd )
-  local session_cookie
-  session_cookie=$(cat "$scriptdir"/.session)
-
-  local output
-  output=$(curl "https://adventofcode.com/$year/day/$day/input"\
-           -H "Cookie: $session_cookie")
-
-  echo "$output"
-}
-
-function get_input_with_cache() {
-  local -r year=$(printf "%d" "$1");
-  local -r day=$(printf "%d" "$2");
-
-  local -r scriptdir=$( cd -- "$( dirname -- "${BASH_SOURCE[0]}" )" &> /dev/null && pwd )
-  mkdir -p "$scriptdir"/.inputs
-  local -r filename="$scriptdir"/.inputs/${year}_${day}.txt
-
-  if [[ -f $filename ]]; then
-    cat "$filename"
-  else
-    get_input "$year" "$day" > "$filename"
-    cat "$filename"
-  fi
-}
-
 if ! hash docopts
 then
 echo "docopts must be available"
@@ -42,7 +11,7 @@ fi
 ##? Usage:
 ##?   run.sh (-h|--help)
 ##?   run.sh test <year> [<day> [<puzzle_number>]] [--implem=<lang>]
-##?   run.sh run <year> <day> [<puzzle_number>] [--implem=<lang>]
+##?   run.sh run <year> [<day> [<puzzle_number>]] [--implem=<lang>]
 ##?
 ##? Options:
 ##?   --implem=<lang>  Implementation of the solution [default: rust].
@@ -60,18 +29,12 @@ implem_rust() {
   local day=$3
   local number=$4
 
+  cd "$(dirname "$0")/rust"
   if [[ $is_test == "true" ]]
   then
-      local filter=""
-      if [[ $year ]]; then filter=$filter"y$year"; fi 
-      if [[ $day ]]; then filter=$filter"::day_$(printf "%02d" "$day")"; fi 
-      cd "$(dirname "$0")/rust"
-      cargo test "$filter"
+      cargo run --release -- test "$year" $day $number
   else
-      local input
-      input=$(get_input_with_cache "$year" "$day")
-      cd "$(dirname "$0")/rust"
-      echo "$input" | cargo run --release -- "$year" "$day" "$number"
+      cargo run --release -- run "$year" $day ${number:+--part "$number"}
   fi
 }
 
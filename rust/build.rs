@@ -0,0 +1,195 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+// Walks the `yYYYY` directories of `dir`, `day_number` tells which of their entries are days
+fn find_by_year(
+    dir: &Path,
+    day_number: impl Fn(&Path) -> Option<u8>,
+) -> BTreeMap<u16, Vec<(u8, PathBuf)>> {
+    let mut years: BTreeMap<u16, Vec<(u8, PathBuf)>> = BTreeMap::new();
+    let Ok(year_entries) = fs::read_dir(dir) else {
+        return years;
+    };
+
+    for year_entry in year_entries {
+        let year_path = year_entry.unwrap().path();
+        let year = year_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix('y'))
+            .and_then(|year| year.parse::<u16>().ok());
+        let year = match (year, year_path.is_dir()) {
+            (Some(year), true) => year,
+            _ => continue,
+        };
+
+        for day_entry in fs::read_dir(&year_path).unwrap() {
+            let day_path = day_entry.unwrap().path();
+            if let Some(day) = day_number(&day_path) {
+                years.entry(year).or_default().push((day, day_path));
+            }
+        }
+    }
+
+    for days in years.values_mut() {
+        days.sort();
+    }
+    years
+}
+
+fn file_name(path: &Path) -> Option<&str> {
+    path.file_name().and_then(|name| name.to_str())
+}
+
+// Every `src/days/yYYYY/day_DD.rs` file is a solution, the registry is generated from them
+fn find_days(days_dir: &Path) -> BTreeMap<u16, Vec<(u8, PathBuf)>> {
+    find_by_year(days_dir, |path| {
+        file_name(path)
+            .and_then(|name| name.strip_prefix("day_"))
+            .and_then(|name| name.strip_suffix(".rs"))
+            .and_then(|day| day.parse::<u8>().ok())
+    })
+}
+
+fn generate(years: &BTreeMap<u16, Vec<(u8, PathBuf)>>) -> String {
+    let mut modules = String::new();
+    let mut registry = String::new();
+
+    for (year, days) in years {
+        // Only the registry is public, the solutions are reached through it
+        modules.push_str(&format!("mod y{} {{\n", year));
+        for (day, path) in days {
+            modules.push_str(&format!(
+                "    #[path = {:?}]\n    pub(super) mod day_{:02};\n",
+                path.display().to_string(),
+                day
+            ));
+            registry.push_str(&format!(
+                "    get_day!({}, {}, y{}::day_{:02}),\n",
+                year, day, year, day
+            ));
+        }
+        modules.push_str("}\n");
+    }
+
+    format!(
+        "{}\n/// Every registered day, sorted by year then day\npub static DAYS: &[Day] = &[\n{}];\n",
+        modules, registry
+    )
+}
+
+// Examples live in `examples/yYYYY/day_DD/<name>/`, with an `input.txt`, the expected `part_N.txt` answers
+// and the `params.txt` puzzle parameters they need
+fn find_examples(examples_dir: &Path) -> BTreeMap<u16, Vec<(u8, PathBuf)>> {
+    find_by_year(examples_dir, |path| {
+        file_name(path)
+            .and_then(|name| name.strip_prefix("day_"))
+            .and_then(|day| day.parse::<u8>().ok())
+            .filter(|_| path.is_dir())
+    })
+}
+
+fn generate_examples(years: &BTreeMap<u16, Vec<(u8, PathBuf)>>) -> String {
+    let include = |path: PathBuf| match path.is_file() {
+        true => format!("Some(include_str!({:?}))", path.display().to_string()),
+        false => "None".to_string(),
+    };
+
+    let mut registry = String::new();
+    for (year, days) in years {
+        for (day, day_path) in days {
+            let mut examples = fs::read_dir(day_path)
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| path.join("input.txt").is_file())
+                .collect::<Vec<_>>();
+            examples.sort();
+
+            for path in examples {
+                registry.push_str(&format!(
+                    "    Example {{\n        year: {},\n        day: {},\n        name: {:?},\n        input: include_str!({:?}),\n        answers: [{}, {}],\n        params: {},\n    }},\n",
+                    year,
+                    day,
+                    file_name(&path).unwrap(),
+                    path.join("input.txt").display().to_string(),
+                    include(path.join("part_1.txt")),
+                    include(path.join("part_2.txt")),
+                    include(path.join("params.txt")),
+                ));
+            }
+        }
+    }
+
+    format!(
+        "/// Every example, sorted by year, day and name\npub static EXAMPLES: &[Example] = &[\n{}];\n",
+        registry
+    )
+}
+
+// The trimmed output of a git command run in `dir`, `None` when git is missing or fails
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// The bench history files its measures under the commit the binary was built from, and tells
+// apart the builds of a tree with uncommitted changes. Both are empty outside of a git checkout
+fn describe_commit(manifest_dir: &Path) {
+    let commit = git(manifest_dir, &["rev-parse", "HEAD"]).unwrap_or_default();
+    let dirty = !commit.is_empty()
+        && git(manifest_dir, &["status", "--porcelain"]).is_none_or(|status| !status.is_empty());
+    println!("cargo:rustc-env=AVC_COMMIT={}", commit);
+    println!("cargo:rustc-env=AVC_DIRTY={}", dirty);
+
+    // A commit, a checkout or a `git add` moves these, an edit of the sources is caught below
+    let mut watched = vec![
+        "HEAD".to_string(),
+        "index".to_string(),
+        "packed-refs".to_string(),
+    ];
+    watched.extend(git(manifest_dir, &["symbolic-ref", "-q", "HEAD"]));
+    for name in watched {
+        if let Some(path) = git(manifest_dir, &["rev-parse", "--git-path", &name]) {
+            let path = manifest_dir.join(path);
+            // Cargo runs the script on every build for a path that does not exist
+            if path.exists() {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+    }
+    for name in ["src", "tests", "build.rs", "Cargo.toml"] {
+        println!(
+            "cargo:rerun-if-changed={}",
+            manifest_dir.join(name).display()
+        );
+    }
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let days_dir = manifest_dir.join("src").join("days");
+    println!("cargo:rerun-if-changed={}", days_dir.display());
+    // The examples are shared with other tooling, so they sit at the root of the repository
+    let examples_dir = manifest_dir.join("..").join("examples");
+    println!("cargo:rerun-if-changed={}", examples_dir.display());
+    describe_commit(&manifest_dir);
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("days.rs"), generate(&find_days(&days_dir))).unwrap();
+    fs::write(
+        out_dir.join("examples.rs"),
+        generate_examples(&find_examples(&examples_dir)),
+    )
+    .unwrap();
+}
@@ -0,0 +1,189 @@
+use std::{
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use avc::utils::{Answer, Execution, SolveError};
+
+// Confirmed answers live in `.answers/` next to the input cache, one file per year, day and part
+pub struct AnswerStore {
+    pub root: PathBuf,
+}
+
+impl AnswerStore {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+        }
+    }
+
+    pub fn answer_path(&self, year: u16, day: u8, puzzle_number: u8) -> PathBuf {
+        self.root
+            .join(".answers")
+            .join(format!("{}_{}_{}.txt", year, day, puzzle_number))
+    }
+
+    pub fn load(&self, year: u16, day: u8, puzzle_number: u8) -> io::Result<Option<String>> {
+        match fs::read_to_string(self.answer_path(year, day, puzzle_number)) {
+            Ok(answer) => Ok(Some(answer.trim_end_matches('\n').to_string())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn record(&self, year: u16, day: u8, puzzle_number: u8, answer: &Answer) -> io::Result<()> {
+        let path = self.answer_path(year, day, puzzle_number);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, format!("{}\n", answer))
+    }
+}
+
+pub enum Verdict {
+    Match,
+    Mismatch { expected: String, actual: String },
+    Failed(SolveError),
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Match => write!(f, "ok"),
+            // Grids are easier to compare one below the other
+            Verdict::Mismatch { expected, actual }
+                if expected.contains('\n') || actual.contains('\n') =>
+            {
+                write!(f, "expected\n{}\nbut got\n{}", expected, actual)
+            }
+            Verdict::Mismatch { expected, actual } => {
+                write!(f, "expected {} but got {}", expected, actual)
+            }
+            Verdict::Failed(e) => write!(f, "failed: {}", e),
+        }
+    }
+}
+
+// Integers are compared by value, so that a recorded `0042` or `+42` matches `42`. The other
+// answers are compared with their `Display`
+fn is_expected(answer: &Answer, expected: &str) -> bool {
+    match answer {
+        Answer::Integer(n) => expected.trim().parse::<i128>() == Ok(*n),
+        answer => answer.to_string() == expected,
+    }
+}
+
+// Compares the answers of an execution with the recorded ones, in the order of `recorded`
+pub fn check(execution: &Execution, recorded: &[(u8, String)]) -> Vec<(u8, Verdict)> {
+    let parts = match &execution.parts {
+        Ok(parts) => parts,
+        Err(e) => {
+            return recorded
+                .iter()
+                .map(|(puzzle_number, _)| (*puzzle_number, Verdict::Failed(e.clone())))
+                .collect()
+        }
+    };
+
+    recorded
+        .iter()
+        .map(|(puzzle_number, expected)| {
+            let part = parts
+                .iter()
+                .find(|part| part.puzzle_number == *puzzle_number);
+            let verdict = match part.map(|part| &part.answer) {
+                Some(Ok(answer)) if is_expected(answer, expected) => Verdict::Match,
+                Some(Ok(answer)) => Verdict::Mismatch {
+                    expected: expected.clone(),
+                    actual: answer.to_string(),
+                },
+                Some(Err(e)) => Verdict::Failed(e.clone()),
+                None => Verdict::Failed(SolveError::new(format!(
+                    "puzzle {} was not executed",
+                    puzzle_number
+                ))),
+            };
+            (*puzzle_number, verdict)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process, time::Duration};
+
+    use avc::utils::PartResult;
+
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("avc-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn execution(answers: Vec<Result<Answer, SolveError>>) -> Execution {
+        let parts = answers
+            .into_iter()
+            .enumerate()
+            .map(|(i, answer)| PartResult {
+                puzzle_number: i as u8 + 1,
+                answer,
+                time: Duration::ZERO,
+                alloc: None,
+            })
+            .collect();
+        Execution {
+            parse_time: Duration::ZERO,
+            parse_alloc: None,
+            parts: Ok(parts),
+        }
+    }
+
+    #[test]
+    fn test_record_and_load() {
+        let store = AnswerStore::new(&temp_root("answers"));
+        assert_eq!(store.load(2022, 10, 2).unwrap(), None);
+
+        let grid = Answer::Grid(vec![vec![true, false], vec![false, true]]);
+        store.record(2022, 10, 2, &grid).unwrap();
+        store.record(2022, 10, 1, &Answer::Integer(13140)).unwrap();
+
+        assert_eq!(store.load(2022, 10, 2).unwrap().unwrap(), "#.\n.#");
+        assert_eq!(store.load(2022, 10, 1).unwrap().unwrap(), "13140");
+    }
+
+    #[test]
+    fn test_check() {
+        let recorded = [(1, "24000".to_string()), (2, "45000".to_string())];
+
+        let verdicts = check(
+            &execution(vec![Ok(Answer::Integer(24000)), Ok(Answer::Integer(41000))]),
+            &recorded,
+        );
+        assert!(matches!(verdicts[0], (1, Verdict::Match)));
+        assert_eq!(verdicts[1].1.to_string(), "expected 45000 but got 41000");
+
+        let padded = [(1, "0042".to_string()), (2, "007".to_string())];
+        let verdicts = check(
+            &execution(vec![Ok(Answer::Integer(42)), Ok(Answer::Text("7".into()))]),
+            &padded,
+        );
+        assert!(matches!(verdicts[0], (1, Verdict::Match)));
+        assert!(matches!(verdicts[1], (2, Verdict::Mismatch { .. })));
+
+        let verdicts = check(
+            &execution(vec![Ok(Answer::Integer(24000)), Err("no".into())]),
+            &recorded,
+        );
+        assert!(matches!(verdicts[1], (2, Verdict::Failed(_))));
+
+        let failed = Execution {
+            parse_time: Duration::ZERO,
+            parse_alloc: None,
+            parts: Err("bad input".into()),
+        };
+        let verdicts = check(&failed, &recorded[1..]);
+        assert!(matches!(&verdicts[..], [(2, Verdict::Failed(_))]));
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use avc::utils::{Execution, SolveError};
+
+use crate::{
+    answers::Verdict,
+    summary::{
+        execution_cells, execution_has_alloc, format_duration, format_table, step_headers,
+        total_time,
+    },
+};
+
+// The expected answers of an input sit next to it, `alice.txt` is checked against `alice.answers`
+const ANSWERS_EXTENSION: &str = "answers";
+
+pub struct InputRun {
+    pub name: String,
+    pub execution: Execution,
+    // Empty when the input has no answers file
+    pub verdicts: Vec<(u8, Verdict)>,
+}
+
+impl InputRun {
+    pub fn is_checked(&self) -> bool {
+        !self.verdicts.is_empty()
+    }
+
+    pub fn passed(&self) -> bool {
+        !self.execution.has_failures()
+            && self
+                .verdicts
+                .iter()
+                .all(|(_, verdict)| matches!(verdict, Verdict::Match))
+    }
+}
+
+// Every file of `dir` but the answers and the hidden ones, sorted by name
+pub fn find_inputs(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut inputs = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_none_or(|name| name.starts_with('.'));
+        let answers = path.extension().is_some_and(|ext| ext == ANSWERS_EXTENSION);
+        if path.is_file() && !hidden && !answers {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+pub fn answers_path(input: &Path) -> PathBuf {
+    input.with_extension(ANSWERS_EXTENSION)
+}
+
+// Each answer follows a `part N:` header, on the same line or below it for the grids:
+//   part 1: 13140
+//   part 2:
+//   ##..##..
+pub fn parse_answers(text: &str) -> Result<Vec<(u8, String)>, SolveError> {
+    let mut answers: Vec<(u8, String)> = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let header = line
+            .strip_prefix("part ")
+            .and_then(|line| line.split_once(':'));
+        match (header, answers.last_mut()) {
+            (Some((number, answer)), _) => {
+                let puzzle_number = match number.trim() {
+                    "1" => 1,
+                    "2" => 2,
+                    number => {
+                        return Err(SolveError::new(format!("unknown puzzle number {}", number))
+                            .at_line(i + 1, line))
+                    }
+                };
+                answers.push((puzzle_number, answer.trim().to_string()));
+            }
+            (None, Some((_, answer))) => {
+                if !answer.is_empty() {
+                    answer.push('\n');
+                }
+                answer.push_str(line);
+            }
+            (None, None) if line.trim().is_empty() => {}
+            (None, None) => {
+                return Err(SolveError::new("expected a `part N:` header").at_line(i + 1, line))
+            }
+        }
+    }
+
+    for (_, answer) in &mut answers {
+        answer.truncate(answer.trim_end().len());
+    }
+    Ok(answers)
+}
+
+fn check_cell(run: &InputRun) -> String {
+    let failed = run
+        .verdicts
+        .iter()
+        .filter(|(_, verdict)| matches!(verdict, Verdict::Mismatch { .. }))
+        .map(|(puzzle_number, _)| puzzle_number.to_string())
+        .collect::<Vec<_>>();
+    match (run.is_checked(), run.execution.has_failures()) {
+        _ if !failed.is_empty() => format!("wrong part {}", failed.join(", ")),
+        (_, true) => "error".to_string(),
+        (true, false) => "ok".to_string(),
+        (false, false) => "unchecked".to_string(),
+    }
+}
+
+// One row per input with the time of its parse and parts, the footer sums it and counts the
+// inputs solved without an error or a wrong answer
+pub fn render_table(runs: &[InputRun]) -> String {
+    let with_alloc = runs.iter().any(|run| execution_has_alloc(&run.execution));
+    let header = std::iter::once("Input")
+        .chain(step_headers(with_alloc))
+        .chain(["Total", "Check"])
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    let passed = runs.iter().filter(|run| run.passed()).count();
+    let total: Duration = runs.iter().map(|run| total_time(&run.execution)).sum();
+    let mut footer = vec![String::new(); header.len()];
+    footer[0] = "Total".to_string();
+    footer[header.len() - 2] = format_duration(total);
+    footer[header.len() - 1] = format!("{}/{} passed", passed, runs.len());
+
+    let rows = runs
+        .iter()
+        .map(|run| {
+            let mut cells = vec![run.name.clone()];
+            cells.extend(execution_cells(&run.execution, with_alloc));
+            cells.push(format_duration(total_time(&run.execution)));
+            cells.push(check_cell(run));
+            cells
+        })
+        .collect();
+    format_table(header, rows, Some(footer))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use avc::utils::{Answer, PartResult};
+
+    use super::*;
+
+    #[test]
+    fn test_find_inputs() {
+        let dir = env::temp_dir().join(format!("avc-batch-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("old")).unwrap();
+        for name in ["bob.txt", "alice.txt", "alice.answers", ".hidden", "carol"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+
+        let names = find_inputs(&dir)
+            .unwrap()
+            .iter()
+            .map(|path| path.file_name().unwrap().to_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["alice.txt", "bob.txt", "carol"]);
+        assert_eq!(answers_path(&dir.join("carol")), dir.join("carol.answers"));
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers("part 1: 13140\npart 2:\n#..#\n.##.\n\n").unwrap();
+        assert_eq!(
+            answers,
+            [(1, "13140".to_string()), (2, "#..#\n.##.".to_string())]
+        );
+
+        let error = parse_answers("part 1: 3\npart 3: 4").unwrap_err();
+        assert_eq!(error.line, Some((2, "part 3: 4".to_string())));
+        assert!(parse_answers("13140").is_err());
+    }
+
+    #[test]
+    fn test_render_table() {
+        let run = |name: &str, answers: [Result<Answer, SolveError>; 2], verdicts| {
+            let parts = answers
+                .into_iter()
+                .enumerate()
+                .map(|(i, answer)| PartResult {
+                    puzzle_number: i as u8 + 1,
+                    answer,
+                    time: Duration::from_millis(1),
+                    alloc: None,
+                })
+                .collect();
+            InputRun {
+                name: name.to_string(),
+                execution: Execution {
+                    parse_time: Duration::from_millis(2),
+                    parse_alloc: None,
+                    parts: Ok(parts),
+                },
+                verdicts,
+            }
+        };
+        let runs = vec![
+            run(
+                "alice.txt",
+                [Ok(3.into()), Ok(4.into())],
+                vec![(1, Verdict::Match), (2, Verdict::Match)],
+            ),
+            run(
+                "bob.txt",
+                [Ok(3.into()), Ok(5.into())],
+                vec![
+                    (1, Verdict::Match),
+                    (
+                        2,
+                        Verdict::Mismatch {
+                            expected: "4".to_string(),
+                            actual: "5".to_string(),
+                        },
+                    ),
+                ],
+            ),
+            run("carol", [Ok(3.into()), Err("no".into())], vec![]),
+        ];
+
+        assert_eq!(
+            render_table(&runs),
+            "Input     | Parse   | Part 1 | Time    | Part 2 | Time    | Total    | Check
+----------+---------+--------+---------+--------+---------+----------+-------------
+alice.txt | 2.00 ms | 3      | 1.00 ms | 4      | 1.00 ms | 4.00 ms  | ok
+bob.txt   | 2.00 ms | 3      | 1.00 ms | 5      | 1.00 ms | 4.00 ms  | wrong part 2
+carol     | 2.00 ms | 3      | 1.00 ms | error  | 1.00 ms | 4.00 ms  | error
+----------+---------+--------+---------+--------+---------+----------+-------------
+Total     |         |        |         |        |         | 12.00 ms | 1/3 passed"
+        );
+    }
+}
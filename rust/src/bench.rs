@@ -0,0 +1,188 @@
+use avc::utils::{
+    bench::{DayBench, Stats},
+    memory::{format_bytes, AllocStats},
+    SolveError,
+};
+
+use crate::summary::{format_duration, format_table};
+
+pub enum BenchOutcome {
+    MissingInput,
+    Measured(Result<DayBench, SolveError>),
+}
+
+pub struct BenchRun {
+    pub year: u16,
+    pub day: u8,
+    pub outcome: BenchOutcome,
+}
+
+impl BenchRun {
+    pub fn has_failures(&self) -> bool {
+        match &self.outcome {
+            BenchOutcome::MissingInput => false,
+            BenchOutcome::Measured(Ok(bench)) => bench.parts.iter().any(|part| part.stats.is_err()),
+            BenchOutcome::Measured(Err(_)) => true,
+        }
+    }
+}
+
+fn stats_cells(stats: &Stats, with_alloc: bool) -> Vec<String> {
+    let mut cells = vec![stats.runs.to_string()];
+    cells.extend(
+        [stats.min, stats.median, stats.mean, stats.stddev]
+            .into_iter()
+            .map(format_duration),
+    );
+    if with_alloc {
+        cells.extend(alloc_cells(stats.alloc));
+    }
+    cells
+}
+
+fn alloc_cells(alloc: Option<AllocStats>) -> Vec<String> {
+    match alloc {
+        Some(alloc) => vec![
+            alloc.allocations.to_string(),
+            format_bytes(alloc.bytes),
+            format_bytes(alloc.peak),
+        ],
+        None => vec![String::new(); 3],
+    }
+}
+
+fn measured_stats(run: &BenchRun) -> Vec<&Stats> {
+    match &run.outcome {
+        BenchOutcome::Measured(Ok(bench)) => std::iter::once(&bench.parse)
+            .chain(
+                bench
+                    .parts
+                    .iter()
+                    .filter_map(|part| part.stats.as_ref().ok()),
+            )
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn row_cells(run: &BenchRun, with_alloc: bool) -> Vec<Vec<String>> {
+    let width = if with_alloc { 7 } else { 4 };
+    let cells = |step: &str, stats: Option<&Stats>, status: &str| {
+        let mut cells = vec![
+            format!("{:04}", run.year),
+            format!("{:02}", run.day),
+            step.to_string(),
+        ];
+        match stats {
+            Some(stats) => cells.extend(stats_cells(stats, with_alloc)),
+            None => {
+                cells.push(status.to_string());
+                cells.extend(vec![String::new(); width]);
+            }
+        }
+        cells
+    };
+
+    match &run.outcome {
+        BenchOutcome::MissingInput => vec![cells("", None, "missing input")],
+        BenchOutcome::Measured(Err(_)) => vec![cells("parse", None, "error")],
+        BenchOutcome::Measured(Ok(bench)) => {
+            std::iter::once(cells("parse", Some(&bench.parse), ""))
+                .chain(bench.parts.iter().map(|part| {
+                    let step = format!("part {}", part.puzzle_number);
+                    cells(&step, part.stats.as_ref().ok(), "error")
+                }))
+                .collect()
+        }
+    }
+}
+
+// The allocation columns are only shown when they were counted, with the `alloc-stats` feature
+pub fn render_table(runs: &[BenchRun]) -> String {
+    let with_alloc = runs
+        .iter()
+        .flat_map(measured_stats)
+        .any(|stats| stats.alloc.is_some());
+
+    let mut header = [
+        "Year", "Day", "Step", "Runs", "Min", "Median", "Mean", "Stddev",
+    ]
+    .map(String::from)
+    .to_vec();
+    if with_alloc {
+        header.extend(["Allocs", "Allocated", "Peak"].map(String::from));
+    }
+    let rows = runs
+        .iter()
+        .flat_map(|run| row_cells(run, with_alloc))
+        .collect();
+    format_table(header, rows, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use avc::utils::bench::PartBench;
+
+    use super::*;
+
+    fn micros(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(|&s| Duration::from_micros(s)).collect()
+    }
+
+    #[test]
+    fn test_render_table() {
+        let stats = Stats::from_samples(&micros(&[1, 3])).unwrap();
+        let mut runs = vec![
+            BenchRun {
+                year: 2022,
+                day: 1,
+                outcome: BenchOutcome::Measured(Ok(DayBench {
+                    parse: stats.clone(),
+                    parts: vec![
+                        PartBench {
+                            puzzle_number: 1,
+                            stats: Ok(stats),
+                        },
+                        PartBench {
+                            puzzle_number: 2,
+                            stats: Err("no".into()),
+                        },
+                    ],
+                })),
+            },
+            BenchRun {
+                year: 2022,
+                day: 2,
+                outcome: BenchOutcome::MissingInput,
+            },
+        ];
+
+        assert_eq!(
+            render_table(&runs),
+            "Year | Day | Step   | Runs          | Min     | Median  | Mean    | Stddev
+-----+-----+--------+---------------+---------+---------+---------+--------
+2022 | 01  | parse  | 2             | 1.00 µs | 2.00 µs | 2.00 µs | 1.00 µs
+2022 | 01  | part 1 | 2             | 1.00 µs | 2.00 µs | 2.00 µs | 1.00 µs
+2022 | 01  | part 2 | error         |         |         |         |
+2022 | 02  |        | missing input |         |         |         |"
+        );
+
+        let BenchOutcome::Measured(Ok(bench)) = &mut runs[0].outcome else {
+            unreachable!()
+        };
+        bench.parse.alloc = Some(AllocStats {
+            allocations: 2,
+            bytes: 4000,
+            peak: 3000,
+        });
+        assert!(render_table(&runs).starts_with(
+            "Year | Day | Step   | Runs          | Min     | Median  | Mean    | Stddev  | Allocs | Allocated | Peak
+-----+-----+--------+---------------+---------+---------+---------+---------+--------+-----------+--------
+2022 | 01  | parse  | 2             | 1.00 µs | 2.00 µs | 2.00 µs | 1.00 µs | 2      | 4.00 kB   | 3.00 kB
+2022 | 01  | part 1 | 2             | 1.00 µs | 2.00 µs | 2.00 µs | 1.00 µs |        |           |
+"
+        ));
+    }
+}
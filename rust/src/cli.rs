@@ -0,0 +1,950 @@
+use std::{path::PathBuf, time::Duration};
+
+use avc::utils::{parse_param, Level, PuzzleContext};
+
+struct CommandSpec {
+    name: &'static str,
+    args: &'static str,
+    about: &'static str,
+    flags: &'static [&'static str],
+}
+
+struct FlagSpec {
+    name: &'static str,
+    value: Option<&'static str>,
+    about: &'static str,
+}
+
+// The help text and the flag validation are both generated from these tables
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "run",
+        args: "[<year> [<day>]]",
+        about: "Run a day, every day of a year, or every day without arguments",
+        flags: &[
+            "--part",
+            "--input",
+            "--inputs",
+            "--example",
+            "--format",
+            "--record",
+            "--all",
+            "--param",
+            "--variant",
+            "--log",
+            "--log-file",
+            "--timeout",
+            "--serial",
+        ],
+    },
+    CommandSpec {
+        name: "test",
+        args: "[<year> [<day> [<part>]]]",
+        about: "Check the answers of the examples of the selected days",
+        flags: &["--timeout"],
+    },
+    CommandSpec {
+        name: "bench",
+        args: "<year> [<day>]",
+        about: "Benchmark the parsing and both parts on the cached inputs",
+        flags: &[
+            "--format",
+            "--param",
+            "--variant",
+            "--log",
+            "--log-file",
+            "--compare",
+            "--threshold",
+            "--timeout",
+        ],
+    },
+    CommandSpec {
+        name: "compare",
+        args: "<year> <day>",
+        about: "Run every variant of the parts on the same input, check they agree and compare their speed",
+        flags: &[
+            "--part",
+            "--input",
+            "--param",
+            "--log",
+            "--log-file",
+            "--timeout",
+        ],
+    },
+    CommandSpec {
+        name: "report",
+        args: "[<year>]",
+        about: "Write an HTML page with the answers, timings, allocations and bench history of the days",
+        flags: &["--output", "--timeout"],
+    },
+    CommandSpec {
+        name: "serve",
+        args: "",
+        about: "Answer HTTP requests to list the days and solve the inputs posted to them",
+        flags: &["--port", "--timeout"],
+    },
+    CommandSpec {
+        name: "fetch",
+        args: "<year> <day>",
+        about: "Download the input of a day into the cache, the curl executable is needed for https",
+        flags: &[],
+    },
+    CommandSpec {
+        name: "list",
+        args: "[<year>]",
+        about: "List the registered days with their cached inputs and recorded answers",
+        flags: &[],
+    },
+    CommandSpec {
+        name: "new",
+        args: "<year> <day>",
+        about: "Create the solution file of a day from the template, and its example to fill in",
+        flags: &[],
+    },
+    CommandSpec {
+        name: "verify",
+        args: "",
+        about: "Check the current solutions against the recorded answers",
+        flags: &["--timeout"],
+    },
+];
+
+const FLAGS: &[FlagSpec] = &[
+    FlagSpec {
+        name: "--part",
+        value: Some("<1|2>"),
+        about: "Only run one part of the day",
+    },
+    FlagSpec {
+        name: "--input",
+        value: Some("<file>"),
+        about: "Read the input from a file instead of the cache, `-` for stdin",
+    },
+    FlagSpec {
+        name: "--inputs",
+        value: Some("<dir>"),
+        about:
+            "Run on every file of a directory, checked against the `<name>.answers` next to them",
+    },
+    FlagSpec {
+        name: "--example",
+        value: None,
+        about: "Run on the examples of the puzzle and check their answers",
+    },
+    FlagSpec {
+        name: "--format",
+        value: Some("<text|json|csv>"),
+        about: "Output format, json and csv have one record per part",
+    },
+    FlagSpec {
+        name: "--record",
+        value: None,
+        about: "Record the answers of the cached input as confirmed, for `avc verify`",
+    },
+    FlagSpec {
+        name: "--all",
+        value: None,
+        about: "Run every registered day",
+    },
+    FlagSpec {
+        name: "--param",
+        value: Some("<name=value>"),
+        about: "Set a parameter of the puzzle, can be repeated",
+    },
+    FlagSpec {
+        name: "--variant",
+        value: Some("<name>"),
+        about: "Solve with another implementation of the parts, listed by `avc list`",
+    },
+    FlagSpec {
+        name: "--compare",
+        value: Some("<commit>"),
+        about: "Fail when a step got slower than in the benches of this commit, built from a clean tree",
+    },
+    FlagSpec {
+        name: "--threshold",
+        value: Some("<percent>"),
+        about: "How much slower a step can get with --compare, 10 by default",
+    },
+    FlagSpec {
+        name: "--output",
+        value: Some("<file>"),
+        about: "Where to write the report, `report.html` by default",
+    },
+    FlagSpec {
+        name: "--port",
+        value: Some("<port>"),
+        about: "Port to listen on, on the local interface only, 8080 by default",
+    },
+    FlagSpec {
+        name: "--log",
+        value: Some("<error|warn|info|debug>"),
+        about: "Show the messages of the solutions up to this level on stderr",
+    },
+    FlagSpec {
+        name: "--log-file",
+        value: Some("<file>"),
+        about: "Write the messages of the solutions to a file, at the debug level by default",
+    },
+    FlagSpec {
+        name: "--timeout",
+        value: Some("<seconds>"),
+        about: "Give up on the parts that run longer, 60 seconds by default for several days, bench, compare and verify",
+    },
+    FlagSpec {
+        name: "--serial",
+        value: None,
+        about: "Run several days one after the other, for timings without interference",
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    // The cached input, downloaded when missing
+    Cache,
+    File(PathBuf),
+    Stdin,
+    Example,
+    // Every file of a directory
+    Directory(PathBuf),
+}
+
+// The solutions are silent unless a level or a file is given
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogOptions {
+    pub level: Option<Level>,
+    pub file: Option<PathBuf>,
+}
+
+// The bench history entries to compare the new measures with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Baseline {
+    pub commit: String,
+    pub threshold_percent: u32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        year: Option<u16>,
+        day: Option<u8>,
+        part: Option<u8>,
+        input: InputSource,
+        format: Format,
+        record: bool,
+        params: PuzzleContext,
+        log: LogOptions,
+        timeout: Option<Duration>,
+        serial: bool,
+    },
+    Test {
+        year: Option<u16>,
+        day: Option<u8>,
+        part: Option<u8>,
+        timeout: Option<Duration>,
+    },
+    Bench {
+        year: u16,
+        day: Option<u8>,
+        format: Format,
+        params: PuzzleContext,
+        log: LogOptions,
+        baseline: Option<Baseline>,
+        timeout: Option<Duration>,
+    },
+    Compare {
+        year: u16,
+        day: u8,
+        part: Option<u8>,
+        input: InputSource,
+        params: PuzzleContext,
+        log: LogOptions,
+        timeout: Option<Duration>,
+    },
+    Report {
+        year: Option<u16>,
+        output: PathBuf,
+        timeout: Option<Duration>,
+    },
+    Serve {
+        port: u16,
+        timeout: Option<Duration>,
+    },
+    Fetch {
+        year: u16,
+        day: u8,
+    },
+    List {
+        year: Option<u16>,
+    },
+    New {
+        year: u16,
+        day: u8,
+    },
+    Verify {
+        timeout: Option<Duration>,
+    },
+    Help(Option<String>),
+}
+
+pub fn help(command: Option<&str>) -> String {
+    let spec = command.and_then(|name| COMMANDS.iter().find(|spec| spec.name == name));
+    let Some(spec) = spec else {
+        let width = COMMANDS
+            .iter()
+            .map(|spec| spec.name.len() + spec.args.len() + 1)
+            .max()
+            .unwrap_or(0);
+        let commands = COMMANDS
+            .iter()
+            .map(|spec| {
+                let usage = format!("{} {}", spec.name, spec.args);
+                format!("  {:<width$}  {}", usage, spec.about, width = width)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        return format!(
+            "Usage: avc <command> [<args>] [<flags>]\n\nCommands:\n{}\n\nRun `avc help <command>` for the flags of a command.",
+            commands
+        );
+    };
+
+    let usage = [spec.name, spec.args]
+        .into_iter()
+        .chain((!spec.flags.is_empty()).then_some("[<flags>]"))
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+    let mut text = format!("Usage: avc {}\n\n{}", usage.join(" "), spec.about);
+    let flags = FLAGS
+        .iter()
+        .filter(|flag| spec.flags.contains(&flag.name))
+        .map(|flag| match flag.value {
+            Some(value) => (format!("{} {}", flag.name, value), flag.about),
+            None => (flag.name.to_string(), flag.about),
+        })
+        .collect::<Vec<_>>();
+    if !flags.is_empty() {
+        let width = flags.iter().map(|(usage, _)| usage.len()).max().unwrap();
+        text.push_str("\n\nFlags:");
+        for (usage, about) in flags {
+            text.push_str(&format!("\n  {:<width$}  {}", usage, about, width = width));
+        }
+    }
+    text
+}
+
+struct Parsed<'a> {
+    positionals: Vec<&'a str>,
+    flags: Vec<(&'static str, Option<&'a str>)>,
+}
+
+impl Parsed<'_> {
+    fn has(&self, name: &str) -> bool {
+        self.flags.iter().any(|(flag, _)| *flag == name)
+    }
+
+    fn values<'b>(&'b self, name: &'b str) -> impl Iterator<Item = &'b str> {
+        self.flags
+            .iter()
+            .filter(move |(flag, _)| *flag == name)
+            .filter_map(|(_, value)| *value)
+    }
+
+    fn value(&self, name: &str) -> Option<&str> {
+        self.flags
+            .iter()
+            .rev()
+            .find(|(flag, _)| *flag == name)
+            .and_then(|(_, value)| *value)
+    }
+}
+
+// Splits the arguments of a command into positionals and the flags it accepts
+fn split_args<'a>(spec: &CommandSpec, args: &'a [String]) -> Result<Parsed<'a>, String> {
+    let mut parsed = Parsed {
+        positionals: vec![],
+        flags: vec![],
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            parsed.positionals.push(arg);
+            continue;
+        }
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (arg.as_str(), None),
+        };
+        let flag = FLAGS
+            .iter()
+            .find(|flag| flag.name == name && spec.flags.contains(&flag.name))
+            .ok_or(format!("unknown flag {} for `avc {}`", name, spec.name))?;
+        let value = match (flag.value, inline_value) {
+            (None, None) => None,
+            (None, Some(_)) => return Err(format!("flag {} takes no value", name)),
+            (Some(_), Some(value)) => Some(value),
+            (Some(_), None) => Some(
+                args.next()
+                    .map(String::as_str)
+                    .ok_or(format!("flag {} expects a value", name))?,
+            ),
+        };
+        parsed.flags.push((flag.name, value));
+    }
+
+    Ok(parsed)
+}
+
+fn parse_year(arg: &str) -> Result<u16, String> {
+    arg.parse::<u16>()
+        .map_err(|_| format!("expected a year number, got {}", arg))
+}
+
+fn parse_day(arg: &str) -> Result<u8, String> {
+    arg.parse::<u8>()
+        .map_err(|_| format!("expected a day number, got {}", arg))
+}
+
+fn parse_year_and_day(
+    positionals: &[&str],
+    max: usize,
+) -> Result<(Option<u16>, Option<u8>), String> {
+    if positionals.len() > max {
+        return Err(format!("unexpected argument {}", positionals[max]));
+    }
+    let year = positionals.first().map(|arg| parse_year(arg)).transpose()?;
+    let day = positionals.get(1).map(|arg| parse_day(arg)).transpose()?;
+    Ok((year, day))
+}
+
+fn parse_params(parsed: &Parsed) -> Result<PuzzleContext, String> {
+    parsed
+        .values("--param")
+        .try_fold(PuzzleContext::default(), |context, param| {
+            let (name, value) = parse_param(param).map_err(|e| e.to_string())?;
+            Ok(context.with_param(name, value))
+        })
+}
+
+fn with_variant(parsed: &Parsed, params: PuzzleContext) -> PuzzleContext {
+    match parsed.value("--variant") {
+        Some(name) => params.with_variant(name),
+        None => params,
+    }
+}
+
+fn parse_part(parsed: &Parsed) -> Result<Option<u8>, String> {
+    match parsed.value("--part") {
+        None => Ok(None),
+        Some("1") => Ok(Some(1)),
+        Some("2") => Ok(Some(2)),
+        Some(part) => Err(format!("unknown puzzle number {}", part)),
+    }
+}
+
+fn parse_input(parsed: &Parsed) -> InputSource {
+    match parsed.value("--input") {
+        Some("-") => InputSource::Stdin,
+        Some(path) => InputSource::File(PathBuf::from(path)),
+        None => InputSource::Cache,
+    }
+}
+
+fn parse_log(parsed: &Parsed) -> Result<LogOptions, String> {
+    Ok(LogOptions {
+        level: parsed
+            .value("--log")
+            .map(|level| level.parse::<Level>().map_err(|e| e.to_string()))
+            .transpose()?,
+        file: parsed.value("--log-file").map(PathBuf::from),
+    })
+}
+
+fn parse_timeout(parsed: &Parsed) -> Result<Option<Duration>, String> {
+    parsed
+        .value("--timeout")
+        .map(|seconds| {
+            seconds
+                .parse::<f64>()
+                .ok()
+                .filter(|seconds| *seconds > 0.0)
+                .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                .ok_or(format!(
+                    "expected a positive number of seconds, got {}",
+                    seconds
+                ))
+        })
+        .transpose()
+}
+
+fn parse_format(parsed: &Parsed) -> Result<Format, String> {
+    match parsed.value("--format") {
+        None | Some("text") => Ok(Format::Text),
+        Some("json") => Ok(Format::Json),
+        Some("csv") => Ok(Format::Csv),
+        Some(format) => Err(format!("unknown format {}", format)),
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    let Some(name) = args.first() else {
+        return Ok(Command::Help(None));
+    };
+    if name == "help" || name == "--help" || name == "-h" {
+        return Ok(Command::Help(args.get(1).cloned()));
+    }
+    let spec = COMMANDS
+        .iter()
+        .find(|spec| spec.name == name)
+        .ok_or(format!("unknown command {}, see `avc help`", name))?;
+    if args[1..].iter().any(|arg| arg == "--help" || arg == "-h") {
+        return Ok(Command::Help(Some(name.clone())));
+    }
+
+    let parsed = split_args(spec, &args[1..])?;
+    let required = |(year, day): (Option<u16>, Option<u8>)| match (year, day) {
+        (Some(year), Some(day)) => Ok((year, day)),
+        (None, _) => Err("expected a year number".to_string()),
+        (_, None) => Err("expected a day number".to_string()),
+    };
+
+    match spec.name {
+        "run" => {
+            let (year, day) = parse_year_and_day(&parsed.positionals, 2)?;
+            let part = parse_part(&parsed)?;
+            let sources = [
+                parsed.has("--input"),
+                parsed.has("--inputs"),
+                parsed.has("--example"),
+            ];
+            if sources.iter().filter(|&&source| source).count() > 1 {
+                return Err("--input, --inputs and --example are exclusive".to_string());
+            }
+            let input = match (parsed.value("--inputs"), parsed.has("--example")) {
+                (Some(dir), _) => InputSource::Directory(PathBuf::from(dir)),
+                (None, true) => InputSource::Example,
+                (None, false) => parse_input(&parsed),
+            };
+            let record = parsed.has("--record");
+            let params = parse_params(&parsed)?;
+
+            // A single day is needed to pick a part, an input, parameters, a variant, or to record answers
+            let single_day = part.is_some()
+                || input != InputSource::Cache
+                || record
+                || params != PuzzleContext::default()
+                || parsed.has("--variant");
+            if day.is_none() && single_day {
+                return Err(
+                    "--part, --input, --inputs, --example, --record, --param and --variant need a day"
+                        .to_string(),
+                );
+            }
+            if record && params != PuzzleContext::default() {
+                return Err("answers solved with other parameters cannot be recorded".to_string());
+            }
+            // Only added after the check above: the variants find the same answers, so the
+            // answers they find can be recorded
+            let params = with_variant(&parsed, params);
+            // `avc verify` checks the recorded answers against the cached input
+            if record && input != InputSource::Cache {
+                return Err("only the answers of the cached input can be recorded".to_string());
+            }
+            if parsed.has("--all") && year.is_some() {
+                return Err("--all cannot be combined with a year".to_string());
+            }
+            if parsed.has("--serial") && day.is_some() {
+                return Err("--serial only applies to several days".to_string());
+            }
+
+            Ok(Command::Run {
+                year,
+                day,
+                part,
+                input,
+                format: parse_format(&parsed)?,
+                record,
+                params,
+                log: parse_log(&parsed)?,
+                timeout: parse_timeout(&parsed)?,
+                serial: parsed.has("--serial"),
+            })
+        }
+        "test" => {
+            let (year, day) = parse_year_and_day(&parsed.positionals, 3)?;
+            let part = parsed
+                .positionals
+                .get(2)
+                .map(|part| match *part {
+                    "1" => Ok(1),
+                    "2" => Ok(2),
+                    part => Err(format!("unknown puzzle number {}", part)),
+                })
+                .transpose()?;
+            Ok(Command::Test {
+                year,
+                day,
+                part,
+                timeout: parse_timeout(&parsed)?,
+            })
+        }
+        "bench" => {
+            let (year, day) = parse_year_and_day(&parsed.positionals, 2)?;
+            let params = parse_params(&parsed)?;
+            // The baselines are measured on the real input, other params solve another puzzle
+            if parsed.has("--compare") && params != PuzzleContext::default() {
+                return Err("--compare cannot be combined with --param".to_string());
+            }
+            let params = with_variant(&parsed, params);
+            if day.is_none() && params != PuzzleContext::default() {
+                return Err("--param and --variant need a day".to_string());
+            }
+            let threshold_percent = parsed
+                .value("--threshold")
+                .map(|percent| {
+                    percent
+                        .parse::<u32>()
+                        .map_err(|_| format!("expected a percentage, got {}", percent))
+                })
+                .transpose()?;
+            let baseline = match (parsed.value("--compare"), threshold_percent) {
+                (Some(commit), threshold_percent) => Some(Baseline {
+                    commit: commit.to_string(),
+                    threshold_percent: threshold_percent.unwrap_or(10),
+                }),
+                (None, Some(_)) => return Err("--threshold needs --compare".to_string()),
+                (None, None) => None,
+            };
+            Ok(Command::Bench {
+                year: year.ok_or("expected a year number")?,
+                day,
+                format: parse_format(&parsed)?,
+                params,
+                log: parse_log(&parsed)?,
+                baseline,
+                timeout: parse_timeout(&parsed)?,
+            })
+        }
+        "compare" => {
+            let (year, day) = required(parse_year_and_day(&parsed.positionals, 2)?)?;
+            Ok(Command::Compare {
+                year,
+                day,
+                part: parse_part(&parsed)?,
+                input: parse_input(&parsed),
+                params: parse_params(&parsed)?,
+                log: parse_log(&parsed)?,
+                timeout: parse_timeout(&parsed)?,
+            })
+        }
+        "report" => {
+            let (year, _) = parse_year_and_day(&parsed.positionals, 1)?;
+            Ok(Command::Report {
+                year,
+                output: PathBuf::from(parsed.value("--output").unwrap_or("report.html")),
+                timeout: parse_timeout(&parsed)?,
+            })
+        }
+        "serve" => {
+            parse_year_and_day(&parsed.positionals, 0)?;
+            let port = parsed
+                .value("--port")
+                .map(|port| {
+                    port.parse::<u16>()
+                        .map_err(|_| format!("expected a port number, got {}", port))
+                })
+                .transpose()?;
+            Ok(Command::Serve {
+                port: port.unwrap_or(8080),
+                timeout: parse_timeout(&parsed)?,
+            })
+        }
+        "fetch" => {
+            let (year, day) = required(parse_year_and_day(&parsed.positionals, 2)?)?;
+            Ok(Command::Fetch { year, day })
+        }
+        "list" => {
+            let (year, _) = parse_year_and_day(&parsed.positionals, 1)?;
+            Ok(Command::List { year })
+        }
+        "new" => {
+            let (year, day) = required(parse_year_and_day(&parsed.positionals, 2)?)?;
+            Ok(Command::New { year, day })
+        }
+        _ => {
+            parse_year_and_day(&parsed.positionals, 0)?;
+            Ok(Command::Verify {
+                timeout: parse_timeout(&parsed)?,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, String> {
+        let args = args
+            .split_whitespace()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        parse_args(&args)
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            parse("run 2022 5 --part 2 --input=-").unwrap(),
+            Command::Run {
+                year: Some(2022),
+                day: Some(5),
+                part: Some(2),
+                input: InputSource::Stdin,
+                format: Format::Text,
+                record: false,
+                params: PuzzleContext::default(),
+                log: LogOptions::default(),
+                timeout: None,
+                serial: false,
+            }
+        );
+        assert_eq!(
+            parse("run --all").unwrap(),
+            Command::Run {
+                year: None,
+                day: None,
+                part: None,
+                input: InputSource::Cache,
+                format: Format::Text,
+                record: false,
+                params: PuzzleContext::default(),
+                log: LogOptions::default(),
+                timeout: None,
+                serial: false,
+            }
+        );
+        assert!(matches!(
+            parse("run 2021 1 --input in.txt").unwrap(),
+            Command::Run { input: InputSource::File(path), record: false, .. } if path.to_str() == Some("in.txt")
+        ));
+        assert!(matches!(
+            parse("run 2022 15 --example --param row=10 --param=max_coord=20").unwrap(),
+            Command::Run { params, .. } if params == PuzzleContext::default()
+                .with_param("row", "10")
+                .with_param("max_coord", "20")
+        ));
+        assert!(matches!(
+            parse("run 2022 10 --inputs accounts").unwrap(),
+            Command::Run { input: InputSource::Directory(path), .. } if path.to_str() == Some("accounts")
+        ));
+        assert!(matches!(
+            parse("run 2022 16 --variant bitmask --record").unwrap(),
+            Command::Run { params, record: true, .. } if params.variant() == Some("bitmask")
+        ));
+        assert!(matches!(
+            parse("run --log info --log-file debug.log").unwrap(),
+            Command::Run { log: LogOptions { level: Some(Level::Info), file: Some(path) }, .. }
+                if path.to_str() == Some("debug.log")
+        ));
+        assert!(matches!(
+            parse("run 2022 --timeout 2.5").unwrap(),
+            Command::Run { timeout: Some(timeout), .. } if timeout == Duration::from_millis(2500)
+        ));
+    }
+
+    #[test]
+    fn test_parse_other_commands() {
+        assert_eq!(
+            parse("fetch 2022 3").unwrap(),
+            Command::Fetch { year: 2022, day: 3 }
+        );
+        assert_eq!(
+            parse("bench 2025").unwrap(),
+            Command::Bench {
+                year: 2025,
+                day: None,
+                format: Format::Text,
+                params: PuzzleContext::default(),
+                log: LogOptions::default(),
+                baseline: None,
+                timeout: None,
+            }
+        );
+        assert!(matches!(
+            parse("bench 2022 --compare abc123 --threshold 25").unwrap(),
+            Command::Bench { baseline: Some(Baseline { commit, threshold_percent: 25 }), .. }
+                if commit == "abc123"
+        ));
+        assert!(matches!(
+            parse("bench 2022 --timeout 10").unwrap(),
+            Command::Bench { timeout: Some(timeout), .. } if timeout == Duration::from_secs(10)
+        ));
+        assert_eq!(
+            parse("compare 2022 16 --part 2 --input in.txt --timeout 5").unwrap(),
+            Command::Compare {
+                year: 2022,
+                day: 16,
+                part: Some(2),
+                input: InputSource::File(PathBuf::from("in.txt")),
+                params: PuzzleContext::default(),
+                log: LogOptions::default(),
+                timeout: Some(Duration::from_secs(5)),
+            }
+        );
+        assert!(matches!(
+            parse("bench 2022 16 --variant bitmask").unwrap(),
+            Command::Bench { params, .. } if params.variant() == Some("bitmask")
+        ));
+        assert_eq!(
+            parse("test 2022 16 2").unwrap(),
+            Command::Test {
+                year: Some(2022),
+                day: Some(16),
+                part: Some(2),
+                timeout: None,
+            }
+        );
+        assert_eq!(
+            parse("report 2022 --output out.html").unwrap(),
+            Command::Report {
+                year: Some(2022),
+                output: PathBuf::from("out.html"),
+                timeout: None,
+            }
+        );
+        assert!(matches!(
+            parse("report").unwrap(),
+            Command::Report { year: None, output, .. } if output.to_str() == Some("report.html")
+        ));
+        assert_eq!(
+            parse("serve --port 9000 --timeout 5").unwrap(),
+            Command::Serve {
+                port: 9000,
+                timeout: Some(Duration::from_secs(5)),
+            }
+        );
+        assert_eq!(parse("list").unwrap(), Command::List { year: None });
+        assert_eq!(parse("verify").unwrap(), Command::Verify { timeout: None });
+        assert_eq!(
+            parse("verify --timeout 5").unwrap(),
+            Command::Verify {
+                timeout: Some(Duration::from_secs(5)),
+            }
+        );
+        assert_eq!(
+            parse("test 2022 --help").unwrap(),
+            Command::Help(Some("test".to_string()))
+        );
+        assert_eq!(parse("").unwrap(), Command::Help(None));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("run 2022 1 --part 3").unwrap_err(),
+            "unknown puzzle number 3"
+        );
+        assert_eq!(
+            parse("fetch 2022 1 --part 1").unwrap_err(),
+            "unknown flag --part for `avc fetch`"
+        );
+        assert_eq!(
+            parse("run 2022 --part").unwrap_err(),
+            "flag --part expects a value"
+        );
+        assert_eq!(
+            parse("bench 2025 --log verbose").unwrap_err(),
+            "unknown log level verbose, expected error, warn, info or debug"
+        );
+        assert_eq!(
+            parse("run --timeout 0").unwrap_err(),
+            "expected a positive number of seconds, got 0"
+        );
+        assert_eq!(
+            parse("run 2022 16 --serial").unwrap_err(),
+            "--serial only applies to several days"
+        );
+        assert_eq!(
+            parse("run 2022 --part 1").unwrap_err(),
+            "--part, --input, --inputs, --example, --record, --param and --variant need a day"
+        );
+        assert_eq!(
+            parse("run 2022 10 --inputs dir --example").unwrap_err(),
+            "--input, --inputs and --example are exclusive"
+        );
+        for args in [
+            "run 2021 1 --input in.txt --record",
+            "run 2021 1 --input - --record",
+            "run 2022 10 --example --record",
+            "run 2022 10 --inputs dir --record",
+        ] {
+            assert_eq!(
+                parse(args).unwrap_err(),
+                "only the answers of the cached input can be recorded",
+                "{}",
+                args
+            );
+        }
+        assert_eq!(
+            parse("run 2022 15 --record --param row=10").unwrap_err(),
+            "answers solved with other parameters cannot be recorded"
+        );
+        assert_eq!(
+            parse("bench 2022 --variant bitmask").unwrap_err(),
+            "--param and --variant need a day"
+        );
+        assert_eq!(parse("compare 2022").unwrap_err(), "expected a day number");
+        assert_eq!(
+            parse("bench 2022 15 --param row=10 --compare abc123").unwrap_err(),
+            "--compare cannot be combined with --param"
+        );
+        assert_eq!(
+            parse("bench 2022 --threshold 5").unwrap_err(),
+            "--threshold needs --compare"
+        );
+        assert_eq!(
+            parse("test 2022 16 3").unwrap_err(),
+            "unknown puzzle number 3"
+        );
+        assert_eq!(
+            parse("run 2022 15 --param row").unwrap_err(),
+            "expected a parameter as name=value, got row"
+        );
+        assert_eq!(parse("fetch 2022").unwrap_err(), "expected a day number");
+        assert_eq!(
+            parse("serve --port http").unwrap_err(),
+            "expected a port number, got http"
+        );
+        assert_eq!(
+            parse("verify 2022").unwrap_err(),
+            "unexpected argument 2022"
+        );
+        assert_eq!(
+            parse("run 2022 1 --format xml").unwrap_err(),
+            "unknown format xml"
+        );
+        assert!(parse("frobnicate").is_err());
+    }
+
+    #[test]
+    fn test_help() {
+        let text = help(None);
+        assert!(COMMANDS.iter().all(|spec| text.contains(spec.about)));
+
+        let text = help(Some("run"));
+        assert!(text.starts_with("Usage: avc run [<year> [<day>]] [<flags>]"));
+        assert!(text.lines().any(|line| line.starts_with("  --part <1|2> ")
+            && line.ends_with("  Only run one part of the day")));
+        assert!(!help(Some("fetch")).contains("Flags:"));
+        assert!(help(Some("serve")).starts_with("Usage: avc serve [<flags>]\n"));
+    }
+}
@@ -0,0 +1,899 @@
+use std::{
+    env, fs,
+    io::{self, IsTerminal, Read},
+    net::TcpListener,
+    panic,
+    path::Path,
+    sync::{Arc, OnceLock},
+    thread,
+    time::Duration,
+};
+
+use rayon::prelude::*;
+
+use avc::{
+    days::{get_day, get_days, DAYS},
+    utils::{
+        bench::BenchConfig, fixtures::get_examples, memory::AllocStats, Day, Execution, Level, Log,
+        PuzzleContext, DEFAULT_VARIANT,
+    },
+};
+
+use crate::{
+    answers::{self, AnswerStore, Verdict},
+    batch::{self, InputRun},
+    bench::{self, BenchOutcome, BenchRun},
+    cli::{Baseline, Format, InputSource, LogOptions},
+    compare,
+    export::{self, Record},
+    history::{self, History},
+    inputs::InputStore,
+    report::{self, DayReport},
+    server,
+    summary::{format_table, render_table, DayOutcome, DayRun},
+    testing::{self, ExampleCheck},
+};
+
+// Several days are run with a deadline by default so that a slow one cannot hang the others
+pub const SUMMARY_TIMEOUT: Duration = Duration::from_secs(60);
+
+// Shown after the timings when the allocations were counted
+fn memory_column(alloc: Option<AllocStats>) -> String {
+    alloc
+        .map(|alloc| format!(" \t Memory: {}", alloc))
+        .unwrap_or_default()
+}
+
+// Returns false if the input could not be parsed or if any puzzle failed
+fn print_execution(year: u16, day: &Day, execution: &Execution) -> bool {
+    outln!(
+        "Year {:04} \t Day {:02} \t Parse time: {:.2e} s{}",
+        year,
+        day.day,
+        execution.parse_time.as_secs_f32(),
+        memory_column(execution.parse_alloc),
+    );
+
+    let parts = match &execution.parts {
+        Ok(parts) => parts,
+        Err(e) => {
+            eprintln!(
+                "Year {:04} Day {:02}: cannot parse input: {}",
+                year, day.day, e
+            );
+            return false;
+        }
+    };
+
+    let mut success = true;
+    for part in parts {
+        match &part.answer {
+            Ok(answer) => outln!(
+                "Year {:04} \t Day {:02} \t Time: {:.2e} s{} \t Puzzle {}:{}{}",
+                year,
+                day.day,
+                part.time.as_secs_f32(),
+                memory_column(part.alloc),
+                part.puzzle_number,
+                // Grids are drawn below the result line
+                if answer.is_multiline() { "\n" } else { " " },
+                answer
+            ),
+            Err(e) => {
+                eprintln!(
+                    "Year {:04} Day {:02}: puzzle {} failed: {}",
+                    year, day.day, part.puzzle_number, e
+                );
+                success = false;
+            }
+        }
+    }
+
+    success
+}
+
+pub fn get_input() -> io::Result<String> {
+    let mut res = String::new();
+    io::stdin().lock().read_to_string(&mut res)?;
+    Ok(res)
+}
+
+// The examples are not read from here, they are run by `run_examples`
+pub fn load_input(
+    store: &InputStore,
+    year: u16,
+    day: u8,
+    source: &InputSource,
+) -> Result<String, String> {
+    match source {
+        InputSource::Cache => store
+            .get(year, day)
+            .map_err(|e| format!("failed to load input: {}", e)),
+        InputSource::File(path) => fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e)),
+        InputSource::Stdin => get_input().map_err(|e| format!("failed to read input: {}", e)),
+        InputSource::Example | InputSource::Directory(_) => {
+            Err("expected a single input".to_string())
+        }
+    }
+}
+
+fn execute_day(
+    day: &Day,
+    input: &str,
+    puzzle_numbers: &[u8],
+    context: &PuzzleContext,
+    timeout: Option<Duration>,
+) -> Execution {
+    match timeout {
+        Some(timeout) => (day.execute_with_timeout)(input, puzzle_numbers, context, timeout),
+        None => (day.execute)(input, puzzle_numbers, context),
+    }
+}
+
+// The successful answers are written to `record` once they are confirmed
+pub fn run(
+    selected_day: &Day,
+    puzzle_number: Option<u8>,
+    input: &str,
+    format: Format,
+    context: &PuzzleContext,
+    timeout: Option<Duration>,
+    record: Option<&AnswerStore>,
+) -> Result<(), String> {
+    let (year, day_number) = (selected_day.year, selected_day.day);
+    context
+        .check_names(selected_day.params)
+        .and_then(|_| context.check_variant(&(selected_day.variants)()))
+        .map_err(|e| e.to_string())?;
+
+    let execution = execute_day(
+        selected_day,
+        input,
+        puzzle_numbers(puzzle_number),
+        context,
+        timeout,
+    );
+
+    if let (Some(store), Ok(parts)) = (record, &execution.parts) {
+        for part in parts {
+            if let Ok(answer) = &part.answer {
+                store
+                    .record(year, day_number, part.puzzle_number, answer)
+                    .map_err(|e| format!("failed to record answer: {}", e))?;
+            }
+        }
+    }
+
+    let success = match format {
+        Format::Text => print_execution(year, selected_day, &execution),
+        format => {
+            print_records(
+                format,
+                &export::execution_records(year, day_number, &execution),
+            );
+            !execution.has_failures()
+        }
+    };
+
+    match success {
+        true => Ok(()),
+        false => Err("some puzzles failed".to_string()),
+    }
+}
+
+// Runs a day on each of its examples and checks the answers given in the puzzle statement,
+// the params of `context` replace the ones of the examples
+pub fn run_examples(
+    year: u16,
+    day_number: u8,
+    puzzle_number: Option<u8>,
+    format: Format,
+    context: &PuzzleContext,
+    timeout: Option<Duration>,
+) -> Result<(), String> {
+    let selected_day = get_day(year, day_number)
+        .ok_or(format!("cannot find day {} for year {}", day_number, year))?;
+    context
+        .check_names(selected_day.params)
+        .and_then(|_| context.check_variant(&(selected_day.variants)()))
+        .map_err(|e| e.to_string())?;
+    let examples = get_examples(year, day_number);
+    if examples.is_empty() {
+        return Err(format!("no example for year {} day {}", year, day_number));
+    }
+
+    let mut success = true;
+    let mut records = vec![];
+    for example in examples {
+        let puzzle_numbers = puzzle_numbers(puzzle_number);
+        let example_context = example
+            .context()
+            .map_err(|e| format!("invalid params for example {}: {}", example.name, e))?
+            .merge(context);
+        let execution = execute_day(
+            selected_day,
+            example.input,
+            puzzle_numbers,
+            &example_context,
+            timeout,
+        );
+
+        match format {
+            Format::Text => {
+                outln!(
+                    "Year {:04} \t Day {:02} \t Example: {}",
+                    year,
+                    day_number,
+                    example.name
+                );
+                success &= print_execution(year, selected_day, &execution);
+            }
+            _ => {
+                success &= !execution.has_failures();
+                records.extend(
+                    export::execution_records(year, day_number, &execution)
+                        .into_iter()
+                        .map(|mut record| {
+                            record.push(("example", example.name.into()));
+                            record
+                        }),
+                );
+            }
+        }
+
+        let expected = puzzle_numbers
+            .iter()
+            .filter_map(|&n| example.answer(n).map(|answer| (n, answer.to_string())))
+            .collect::<Vec<_>>();
+        for (n, verdict) in answers::check(&execution, &expected) {
+            let line = format!(
+                "Year {:04} Day {:02} Example {} Puzzle {}: {}",
+                year, day_number, example.name, n, verdict
+            );
+            match (format, &verdict) {
+                (Format::Text, _) => outln!("{}", line),
+                (_, Verdict::Match) => {}
+                // Keeps the records alone on stdout
+                _ => eprintln!("{}", line),
+            }
+            success &= matches!(verdict, Verdict::Match);
+        }
+    }
+
+    if format != Format::Text {
+        print_records(format, &records);
+    }
+    match success {
+        true => Ok(()),
+        false => Err("some examples do not match".to_string()),
+    }
+}
+
+// Runs a day on every input of `dir`, the ones with an answers file are checked against it
+pub fn run_inputs(
+    selected_day: &Day,
+    dir: &Path,
+    puzzle_number: Option<u8>,
+    format: Format,
+    context: &PuzzleContext,
+    timeout: Option<Duration>,
+) -> Result<(), String> {
+    let (year, day_number) = (selected_day.year, selected_day.day);
+    context
+        .check_names(selected_day.params)
+        .and_then(|_| context.check_variant(&(selected_day.variants)()))
+        .map_err(|e| e.to_string())?;
+    let paths =
+        batch::find_inputs(dir).map_err(|e| format!("cannot read {}: {}", dir.display(), e))?;
+    if paths.is_empty() {
+        return Err(format!("no input in {}", dir.display()));
+    }
+
+    let puzzle_numbers = puzzle_numbers(puzzle_number);
+    let mut runs = vec![];
+    for path in paths {
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        let input = fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        let answers_path = batch::answers_path(&path);
+        let expected = match fs::read_to_string(&answers_path) {
+            Ok(text) => batch::parse_answers(&text)
+                .map_err(|e| format!("invalid answers in {}: {}", answers_path.display(), e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(format!("failed to read {}: {}", answers_path.display(), e)),
+        };
+        let expected = expected
+            .into_iter()
+            .filter(|(n, _)| puzzle_numbers.contains(n))
+            .collect::<Vec<_>>();
+
+        let execution = execute_day(selected_day, &input, puzzle_numbers, context, timeout);
+        let verdicts = answers::check(&execution, &expected);
+        runs.push(InputRun {
+            name,
+            execution,
+            verdicts,
+        });
+    }
+
+    match format {
+        Format::Text => outln!("{}", batch::render_table(&runs)),
+        format => {
+            let records = runs
+                .iter()
+                .flat_map(|run| {
+                    export::execution_records(year, day_number, &run.execution)
+                        .into_iter()
+                        .map(|mut record| {
+                            record.push(("input", run.name.as_str().into()));
+                            record
+                        })
+                })
+                .collect::<Vec<_>>();
+            print_records(format, &records);
+        }
+    }
+
+    for run in &runs {
+        if let Err(e) = &run.execution.parts {
+            eprintln!(
+                "Year {:04} Day {:02} Input {}: cannot parse input: {}",
+                year, day_number, run.name, e
+            );
+        }
+        for (n, verdict) in &run.verdicts {
+            if !matches!(verdict, Verdict::Match) {
+                eprintln!(
+                    "Year {:04} Day {:02} Input {} Puzzle {}: {}",
+                    year, day_number, run.name, n, verdict
+                );
+            }
+        }
+    }
+
+    match runs.iter().all(InputRun::passed) {
+        true => Ok(()),
+        false => Err("some inputs failed".to_string()),
+    }
+}
+
+fn puzzle_numbers(puzzle_number: Option<u8>) -> &'static [u8] {
+    match puzzle_number {
+        None => &[1, 2],
+        Some(1) => &[1],
+        Some(_) => &[2],
+    }
+}
+
+fn print_records(format: Format, records: &[Record]) {
+    match format {
+        Format::Json => outln!("{}", export::to_json(records)),
+        Format::Csv => outln!("{}", export::to_csv(records)),
+        Format::Text => unreachable!("text output is not made of records"),
+    }
+}
+
+const DAY_THREAD: &str = "avc-day";
+
+// The days only wait for their worker thread, they get their own pool so that the solutions
+// using rayon are not starved by them. It is started once and shared by the commands
+fn day_pool() -> Result<&'static rayon::ThreadPool, String> {
+    static POOL: OnceLock<Result<rayon::ThreadPool, String>> = OnceLock::new();
+    POOL.get_or_init(|| {
+        rayon::ThreadPoolBuilder::new()
+            .thread_name(|index| format!("{}-{}", DAY_THREAD, index))
+            .build()
+            .map_err(|e| format!("cannot start the thread pool: {}", e))
+    })
+    .as_ref()
+    .map_err(Clone::clone)
+}
+
+// The panics of the solutions are caught and reported as their failure, they are not printed
+// while `run` runs. Those of the runner, on the main thread or on the day pool, still are
+fn with_quiet_solutions<T>(run: impl FnOnce() -> T) -> T {
+    let previous = Arc::new(panic::take_hook());
+    let hook = Arc::clone(&previous);
+    panic::set_hook(Box::new(move |info| {
+        let name = thread::current().name().unwrap_or_default().to_string();
+        if name == "main" || name.starts_with(DAY_THREAD) {
+            hook(info);
+        }
+    }));
+    let result = run();
+    // The workers left behind after their timeout print their panics again
+    let _ = panic::take_hook();
+    panic::set_hook(Box::new(move |info| previous(info)));
+    result
+}
+
+// Runs every given day on its cached input, the days without an input are not executed.
+// The days run in parallel unless `serial` is set
+pub fn run_days(
+    days: &[&Day],
+    context: &PuzzleContext,
+    timeout: Duration,
+    serial: bool,
+) -> Result<Vec<DayRun>, String> {
+    let store = InputStore::from_env();
+
+    let run_day = |day: &&Day| -> Result<DayRun, String> {
+        let input = store
+            .load_cached(day.year, day.day)
+            .map_err(|e| format!("failed to load input: {}", e))?;
+        let outcome = match input {
+            Some(input) => DayOutcome::Executed((day.execute_with_timeout)(
+                &input,
+                &[1, 2],
+                context,
+                timeout,
+            )),
+            None => DayOutcome::MissingInput,
+        };
+        Ok(DayRun {
+            year: day.year,
+            day: day.day,
+            outcome,
+        })
+    };
+    match serial {
+        true => with_quiet_solutions(|| days.iter().map(run_day).collect()),
+        false => {
+            let pool = day_pool()?;
+            with_quiet_solutions(|| pool.install(|| days.par_iter().map(run_day).collect()))
+        }
+    }
+}
+
+// Prints the answers and timings of every given day on its cached input, days without an input
+// are skipped
+pub fn run_summary(
+    days: &[&Day],
+    format: Format,
+    context: &PuzzleContext,
+    timeout: Duration,
+    serial: bool,
+) -> Result<(), String> {
+    let runs = run_days(days, context, timeout, serial)?;
+
+    if format != Format::Text {
+        let records = runs
+            .iter()
+            .flat_map(export::run_records)
+            .collect::<Vec<_>>();
+        print_records(format, &records);
+    } else {
+        outln!("{}", render_table(&runs));
+    }
+
+    // Errors and grids do not fit in the table
+    for run in runs.iter().filter(|_| format == Format::Text) {
+        let DayOutcome::Executed(execution) = &run.outcome else {
+            continue;
+        };
+        let parts = match &execution.parts {
+            Ok(parts) => parts,
+            Err(e) => {
+                eprintln!(
+                    "Year {:04} Day {:02}: cannot parse input: {}",
+                    run.year, run.day, e
+                );
+                continue;
+            }
+        };
+        for part in parts {
+            match &part.answer {
+                Ok(answer) if answer.is_multiline() => outln!(
+                    "\nYear {:04} Day {:02} Puzzle {}:\n{}",
+                    run.year,
+                    run.day,
+                    part.puzzle_number,
+                    answer
+                ),
+                Ok(_) => {}
+                Err(e) => eprintln!(
+                    "Year {:04} Day {:02}: puzzle {} failed: {}",
+                    run.year, run.day, part.puzzle_number, e
+                ),
+            }
+        }
+    }
+
+    match runs.iter().any(DayRun::has_failures) {
+        true => Err("some puzzles failed".to_string()),
+        false => Ok(()),
+    }
+}
+
+// Appends the measures to the bench history, and compares them with the ones of `baseline`.
+// Returns the number of steps slower than the threshold
+fn record_history(
+    store: &InputStore,
+    runs: &[BenchRun],
+    context: &PuzzleContext,
+    baseline: Option<&Baseline>,
+    format: Format,
+) -> Result<usize, String> {
+    let history = History::new(&store.root);
+    // Loaded before appending, so that a commit can be compared with itself
+    let previous = history
+        .load()
+        .map_err(|e| format!("failed to load the bench history: {}", e))?;
+    let entries = history::entries(
+        runs,
+        history::build_commit(),
+        history::BUILD_DIRTY,
+        context,
+        history::now(),
+    );
+    history
+        .append(&entries)
+        .map_err(|e| format!("failed to write the bench history: {}", e))?;
+
+    let Some(baseline) = baseline else {
+        return Ok(0);
+    };
+    let reference = history::baseline(&previous, &baseline.commit)?;
+    let changes = history::compare(&reference, &entries);
+    let table = history::render_changes(&changes, baseline.threshold_percent);
+    match format {
+        Format::Text => outln!("\n{}", table),
+        // Keeps the records alone on stdout
+        _ => eprintln!("{}", table),
+    }
+    Ok(changes
+        .iter()
+        .filter(|change| change.is_regression(baseline.threshold_percent))
+        .count())
+}
+
+// Benchmarks every given day on its cached input, days without an input are skipped. The parts
+// that do not answer within `timeout` on their first run are not measured
+pub fn run_bench(
+    days: &[&Day],
+    format: Format,
+    context: &PuzzleContext,
+    baseline: Option<&Baseline>,
+    timeout: Duration,
+) -> Result<(), String> {
+    let store = InputStore::from_env();
+    let config = BenchConfig::default();
+
+    let mut runs = vec![];
+    for day in days {
+        context
+            .check_names(day.params)
+            .and_then(|_| context.check_variant(&(day.variants)()))
+            .map_err(|e| e.to_string())?;
+        let input = store
+            .load_cached(day.year, day.day)
+            .map_err(|e| format!("failed to load input: {}", e))?;
+        let outcome = match input {
+            Some(input) => {
+                let execution = (day.execute_with_timeout)(&input, &[1, 2], context, timeout);
+                BenchOutcome::Measured((day.bench)(&input, &execution, &config, context))
+            }
+            None => BenchOutcome::MissingInput,
+        };
+        runs.push(BenchRun {
+            year: day.year,
+            day: day.day,
+            outcome,
+        });
+    }
+
+    if format != Format::Text {
+        let records = runs
+            .iter()
+            .flat_map(export::bench_records)
+            .collect::<Vec<_>>();
+        print_records(format, &records);
+    } else {
+        outln!("{}", bench::render_table(&runs));
+    }
+
+    for run in runs.iter().filter(|_| format == Format::Text) {
+        let BenchOutcome::Measured(measured) = &run.outcome else {
+            continue;
+        };
+        let parts = match measured {
+            Ok(measured) => &measured.parts,
+            Err(e) => {
+                eprintln!(
+                    "Year {:04} Day {:02}: cannot parse input: {}",
+                    run.year, run.day, e
+                );
+                continue;
+            }
+        };
+        for part in parts {
+            if let Err(e) = &part.stats {
+                eprintln!(
+                    "Year {:04} Day {:02}: puzzle {} failed: {}",
+                    run.year, run.day, part.puzzle_number, e
+                );
+            }
+        }
+    }
+
+    let regressions = record_history(&store, &runs, context, baseline, format)?;
+    match (runs.iter().any(BenchRun::has_failures), baseline) {
+        (true, _) => Err("some puzzles failed".to_string()),
+        (false, Some(baseline)) if regressions > 0 => Err(format!(
+            "{} steps got slower than on commit {}",
+            regressions, baseline.commit
+        )),
+        (false, _) => Ok(()),
+    }
+}
+
+// Runs the days like `run_summary`, checks them against the recorded answers and writes the
+// page of `report::render_html` with their bench history
+pub fn report(days: &[&Day], output: &Path, timeout: Duration) -> Result<(), String> {
+    let store = InputStore::from_env();
+    let answers = AnswerStore::new(&store.root);
+    let history = History::new(&store.root)
+        .load()
+        .map_err(|e| format!("failed to load the bench history: {}", e))?;
+
+    let mut reports = vec![];
+    for run in run_days(days, &PuzzleContext::default(), timeout, false)? {
+        let mut recorded = vec![];
+        for puzzle_number in [1, 2] {
+            let answer = answers
+                .load(run.year, run.day, puzzle_number)
+                .map_err(|e| format!("failed to load answer: {}", e))?;
+            if let Some(answer) = answer {
+                recorded.push((puzzle_number, answer));
+            }
+        }
+        let verdicts = match &run.outcome {
+            DayOutcome::Executed(execution) => answers::check(execution, &recorded),
+            DayOutcome::MissingInput => vec![],
+        };
+        reports.push(DayReport { run, verdicts });
+    }
+
+    let commit = history::build_commit().map(|commit| match history::BUILD_DIRTY {
+        true => format!("{} with uncommitted changes", commit),
+        false => commit.to_string(),
+    });
+    let html = report::render_html(&reports, &history, commit.as_deref());
+    fs::write(output, html).map_err(|e| format!("cannot write {}: {}", output.display(), e))?;
+    outln!("{}", output.display());
+    Ok(())
+}
+
+// Serves the days over HTTP on the local interface until the listener fails, see `server::handle`
+pub fn serve(port: u16, timeout: Duration) -> Result<(), String> {
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .map_err(|e| format!("cannot listen on port {}: {}", port, e))?;
+    eprintln!("Listening on http://127.0.0.1:{}", port);
+    server::serve(listener, timeout).map_err(|e| format!("cannot accept connections: {}", e))
+}
+
+// Runs every variant of the selected parts, fails when they do not all find the same answer
+pub fn compare(
+    day: &Day,
+    puzzle_number: Option<u8>,
+    input: &str,
+    context: &PuzzleContext,
+    timeout: Duration,
+) -> Result<(), String> {
+    context.check_names(day.params).map_err(|e| e.to_string())?;
+    let parts = compare::compare(
+        day,
+        input,
+        puzzle_numbers(puzzle_number),
+        &BenchConfig::default(),
+        context,
+        timeout,
+    )
+    .map_err(|e| format!("cannot parse input: {}", e))?;
+    outln!("{}", compare::render_table(&parts));
+
+    let mut success = true;
+    for part in &parts {
+        for variant in &part.variants {
+            if let Err(e) = &variant.answer {
+                eprintln!(
+                    "Year {:04} Day {:02}: puzzle {} failed with variant {}: {}",
+                    day.year, day.day, part.puzzle_number, variant.name, e
+                );
+            }
+        }
+        if !part.agrees() {
+            eprintln!(
+                "Year {:04} Day {:02}: the variants of puzzle {} disagree",
+                day.year, day.day, part.puzzle_number
+            );
+            success = false;
+        }
+    }
+
+    match success {
+        true => Ok(()),
+        false => Err("some variants do not agree".to_string()),
+    }
+}
+
+// Re-runs every day with a recorded answer and reports the answers that changed
+pub fn verify(timeout: Duration) -> Result<(), String> {
+    let inputs = InputStore::from_env();
+    let answers = AnswerStore::new(&inputs.root);
+
+    let (mut checked, mut failed) = (0, 0);
+    for day in DAYS {
+        let mut recorded = vec![];
+        for puzzle_number in [1, 2] {
+            let answer = answers
+                .load(day.year, day.day, puzzle_number)
+                .map_err(|e| format!("failed to load answer: {}", e))?;
+            if let Some(answer) = answer {
+                recorded.push((puzzle_number, answer));
+            }
+        }
+        if recorded.is_empty() {
+            continue;
+        }
+        checked += recorded.len();
+
+        let input = inputs
+            .load_cached(day.year, day.day)
+            .map_err(|e| format!("failed to load input: {}", e))?;
+        let Some(input) = input else {
+            eprintln!(
+                "Year {:04} Day {:02}: answers are recorded but the input is missing",
+                day.year, day.day
+            );
+            failed += recorded.len();
+            continue;
+        };
+
+        let puzzle_numbers = recorded.iter().map(|(n, _)| *n).collect::<Vec<_>>();
+        let execution =
+            (day.execute_with_timeout)(&input, &puzzle_numbers, &PuzzleContext::default(), timeout);
+        for (puzzle_number, verdict) in answers::check(&execution, &recorded) {
+            outln!(
+                "Year {:04} Day {:02} Puzzle {}: {}",
+                day.year,
+                day.day,
+                puzzle_number,
+                verdict
+            );
+            if !matches!(verdict, Verdict::Match) {
+                failed += 1;
+            }
+        }
+    }
+
+    outln!("{}/{} recorded answers match", checked - failed, checked);
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} answers do not match", failed)),
+    }
+}
+
+// Prints the registered days with the state of their cached input and recorded answers
+pub fn list(year: Option<u16>) -> Result<(), String> {
+    let inputs = InputStore::from_env();
+    let answers = AnswerStore::new(&inputs.root);
+
+    let mut rows = vec![];
+    for day in DAYS
+        .iter()
+        .filter(|day| year.is_none_or(|year| day.year == year))
+    {
+        let input = match inputs.cache_path(day.year, day.day).exists() {
+            true => "cached",
+            false => "missing",
+        };
+        let mut recorded = 0;
+        for puzzle_number in [1, 2] {
+            if answers
+                .load(day.year, day.day, puzzle_number)
+                .map_err(|e| format!("failed to load answer: {}", e))?
+                .is_some()
+            {
+                recorded += 1;
+            }
+        }
+        // The other implementations with the parts they solve, `default` solves both
+        let mut variants: Vec<(&str, Vec<String>)> = vec![];
+        for (name, puzzle_number) in (day.variants)() {
+            match variants.iter_mut().find(|(variant, _)| *variant == name) {
+                Some((_, parts)) => parts.push(puzzle_number.to_string()),
+                None => variants.push((name, vec![puzzle_number.to_string()])),
+            }
+        }
+        let variants = variants
+            .into_iter()
+            .filter(|(name, _)| *name != DEFAULT_VARIANT)
+            .map(|(name, parts)| format!("{} ({})", name, parts.join(", ")))
+            .collect::<Vec<_>>();
+        rows.push(vec![
+            format!("{:04}", day.year),
+            format!("{:02}", day.day),
+            input.to_string(),
+            format!("{}/2", recorded),
+            variants.join(", "),
+        ]);
+    }
+
+    let header = ["Year", "Day", "Input", "Answers", "Variants"]
+        .map(String::from)
+        .to_vec();
+    outln!("{}", format_table(header, rows, None));
+    Ok(())
+}
+
+// Checks the examples of the selected days against the answers of the statements, they are
+// embedded in the binary so that nothing else is needed
+pub fn test(days: &[&Day], puzzle_number: Option<u8>, timeout: Duration) -> Result<(), String> {
+    let check_day = |day: &&Day| {
+        let examples = get_examples(day.year, day.day);
+        if examples.is_empty() {
+            return vec![ExampleCheck {
+                year: day.year,
+                day: day.day,
+                example: None,
+                parts: vec![],
+            }];
+        }
+        examples
+            .into_iter()
+            .map(|example| {
+                testing::check_example(day, example, puzzle_numbers(puzzle_number), timeout)
+            })
+            .collect::<Vec<_>>()
+    };
+    // Like `run_days`, the days waiting for their worker thread run on the day pool
+    let checks = day_pool()?.install(|| days.par_iter().flat_map(check_day).collect::<Vec<_>>());
+
+    // The colors are left out when the output is not read on a terminal
+    let color = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+    outln!("{}", testing::render_matrix(&checks, color));
+
+    for check in &checks {
+        for part in &check.parts {
+            if !matches!(part.verdict, Verdict::Match) {
+                eprintln!(
+                    "Year {:04} Day {:02} Example {} Puzzle {}: {}",
+                    check.year,
+                    check.day,
+                    check.example.unwrap_or_default(),
+                    part.puzzle_number,
+                    part.verdict
+                );
+            }
+        }
+    }
+
+    match checks.iter().all(ExampleCheck::passed) {
+        true => Ok(()),
+        false => Err("some examples do not match".to_string()),
+    }
+}
+
+pub fn open_log(options: &LogOptions) -> Result<Log, String> {
+    match (options.level, &options.file) {
+        (None, None) => Ok(Log::default()),
+        (level, None) => Ok(Log::stderr(level.unwrap_or(Level::Info))),
+        (level, Some(path)) => Log::file(level.unwrap_or(Level::Debug), path)
+            .map_err(|e| format!("cannot create {}: {}", path.display(), e)),
+    }
+}
+
+pub fn selected_days(year: u16, day: Option<u8>) -> Result<Vec<&'static Day>, String> {
+    let days = match day {
+        Some(day) => {
+            vec![get_day(year, day).ok_or(format!("cannot find day {} for year {}", day, year))?]
+        }
+        None => get_days(year),
+    };
+    match days.is_empty() {
+        true => Err(format!("no registered day for year {}", year)),
+        false => Ok(days),
+    }
+}
@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use avc::utils::{
+    bench::{BenchConfig, Stats},
+    Answer, Day, PuzzleContext, SolveError,
+};
+
+use crate::summary::{answer_cell, format_duration, format_table};
+
+pub struct VariantRun {
+    pub name: &'static str,
+    pub answer: Result<Answer, SolveError>,
+    // Only measured when an answer was found
+    pub stats: Option<Result<Stats, SolveError>>,
+}
+
+pub struct PartComparison {
+    pub puzzle_number: u8,
+    pub variants: Vec<VariantRun>,
+}
+
+impl PartComparison {
+    // Every variant found an answer, and the same one
+    pub fn agrees(&self) -> bool {
+        let mut answers = self.variants.iter().map(|variant| variant.answer.as_ref());
+        match answers.next() {
+            Some(Ok(first)) => answers.all(|answer| answer == Ok(first)),
+            _ => false,
+        }
+    }
+
+    fn fastest(&self) -> Option<&Stats> {
+        self.variants
+            .iter()
+            .filter_map(|variant| variant.stats.as_ref()?.as_ref().ok())
+            .min_by_key(|stats| stats.median)
+    }
+}
+
+// Solves each part with each of its variants, giving up after `timeout`, then benchmarks the ones
+// that found an answer. Fails as a whole when the input cannot be parsed
+pub fn compare(
+    day: &Day,
+    input: &str,
+    puzzle_numbers: &[u8],
+    config: &BenchConfig,
+    context: &PuzzleContext,
+    timeout: Duration,
+) -> Result<Vec<PartComparison>, SolveError> {
+    let variants = (day.variants)();
+    puzzle_numbers
+        .iter()
+        .map(|&puzzle_number| {
+            let variants = variants
+                .iter()
+                .filter(|(_, n)| *n == puzzle_number)
+                .map(|&(name, _)| {
+                    let context = context.clone().with_variant(name);
+                    let execution =
+                        (day.execute_with_timeout)(input, &[puzzle_number], &context, timeout);
+                    let stats = match &execution.parts {
+                        Ok(parts) if parts[0].answer.is_ok() => Some(
+                            (day.bench)(input, &execution, config, &context)
+                                .and_then(|mut bench| bench.parts.remove(0).stats),
+                        ),
+                        _ => None,
+                    };
+                    let answer = execution.parts?.remove(0).answer;
+                    Ok(VariantRun {
+                        name,
+                        answer,
+                        stats,
+                    })
+                })
+                .collect::<Result<_, SolveError>>()?;
+            Ok(PartComparison {
+                puzzle_number,
+                variants,
+            })
+        })
+        .collect()
+}
+
+// The median of each variant relative to the fastest one of its part
+pub fn render_table(parts: &[PartComparison]) -> String {
+    let header = ["Part", "Variant", "Answer", "Median", "Relative"]
+        .map(String::from)
+        .to_vec();
+    let rows = parts
+        .iter()
+        .flat_map(|part| {
+            let fastest = part.fastest();
+            part.variants.iter().map(move |variant| {
+                let stats = variant.stats.as_ref().and_then(|stats| stats.as_ref().ok());
+                let (median, relative) = match (stats, fastest) {
+                    (Some(stats), Some(fastest)) => (
+                        format_duration(stats.median),
+                        format!(
+                            "{:.2}x",
+                            stats.median.as_secs_f64() / fastest.median.as_secs_f64().max(1e-12)
+                        ),
+                    ),
+                    _ => (String::new(), String::new()),
+                };
+                vec![
+                    part.puzzle_number.to_string(),
+                    variant.name.to_string(),
+                    match &variant.answer {
+                        Ok(answer) => answer_cell(answer),
+                        Err(_) => "error".to_string(),
+                    },
+                    median,
+                    relative,
+                ]
+            })
+        })
+        .collect();
+    format_table(header, rows, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(name: &'static str, answer: Result<Answer, SolveError>, micros: u64) -> VariantRun {
+        let stats = Stats::from_samples(&[Duration::from_micros(micros)]).unwrap();
+        VariantRun {
+            name,
+            stats: answer.is_ok().then_some(Ok(stats)),
+            answer,
+        }
+    }
+
+    #[test]
+    fn test_agrees() {
+        let part = |variants| PartComparison {
+            puzzle_number: 2,
+            variants,
+        };
+        assert!(part(vec![run("default", Ok(1707.into()), 40)]).agrees());
+        assert!(part(vec![
+            run("default", Ok(1707.into()), 40),
+            run("bitmask", Ok(1707.into()), 10)
+        ])
+        .agrees());
+        assert!(!part(vec![
+            run("default", Ok(1707.into()), 40),
+            run("bitmask", Ok(1706.into()), 10)
+        ])
+        .agrees());
+        assert!(!part(vec![
+            run("default", Ok(1707.into()), 40),
+            run("bitmask", Err("no".into()), 10)
+        ])
+        .agrees());
+    }
+
+    #[test]
+    fn test_render_table() {
+        let parts = vec![PartComparison {
+            puzzle_number: 2,
+            variants: vec![
+                run("default", Ok(1707.into()), 40),
+                run("bitmask", Ok(1707.into()), 10),
+                run("greedy", Err("no".into()), 1),
+            ],
+        }];
+
+        assert_eq!(
+            render_table(&parts),
+            "Part | Variant | Answer | Median   | Relative
+-----+---------+--------+----------+---------
+2    | default | 1707   | 40.00 µs | 4.00x
+2    | bitmask | 1707   | 10.00 µs | 1.00x
+2    | greedy  | error  |          |"
+        );
+    }
+}
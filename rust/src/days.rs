@@ -1,15 +1,39 @@
-use crate::utils::Day;
-use core::panic;
-
-pub mod y2021;
-pub mod y2022;
-pub mod y2025;
-
-pub fn get_days(year: u16) -> Vec<Day> {
-    match year {
-        2021 => y2021::get_days(),
-        2022 => y2022::get_days(),
-        2025 => y2025::get_days(),
-        _ => panic!("unhandled year {}", year),
+use crate::utils::{get_day, Day};
+
+// Generated by build.rs from the `days/yYYYY/day_DD.rs` files, sorted by year then day
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+/// The registered day of `year`, `None` when it is not solved
+pub fn get_day(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+/// The registered days of `year`, in order
+pub fn get_days(year: u16) -> Vec<&'static Day> {
+    DAYS.iter().filter(|d| d.year == year).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_sorted() {
+        assert!(DAYS
+            .windows(2)
+            .all(|w| (w[0].year, w[0].day) < (w[1].year, w[1].day)));
+    }
+
+    #[test]
+    fn test_get_day() {
+        assert_eq!(get_day(2022, 17).map(|d| (d.year, d.day)), Some((2022, 17)));
+        assert!(get_day(2023, 1).is_none());
+        assert!(get_day(2021, 6).is_none());
+    }
+
+    #[test]
+    fn test_get_days() {
+        assert_eq!(get_days(2021).len(), 5);
+        assert!(get_days(2023).is_empty());
     }
 }
@@ -1,15 +1,51 @@
-pub fn puzzle_1(input: &str) -> String {}
+// Template used by `avc new <year> <day>`, YEAR and DAY are replaced when the day is created
+// https://adventofcode.com/YEAR/day/DAY
 
-pub fn puzzle_2(input: &str) -> String {}
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
+fn parse_input(input: &str) -> Result<Vec<String>, SolveError> {
+    parse_lines(input, |line| Ok(line.to_string()))
+}
+
+fn puzzle_1(_input: &[String]) -> Result<Answer, SolveError> {
+    Err(SolveError::new("puzzle 1 is not solved yet"))
+}
+
+fn puzzle_2(_input: &[String]) -> Result<Answer, SolveError> {
+    Err(SolveError::new("puzzle 2 is not solved yet"))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
+}
 
 #[cfg(test)]
 mod tests {
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
-    fn test_puzzle_1() {}
+    fn test_puzzle_1() {
+        assert_example::<Puzzle>(YEAR, DAY, "example", 1);
+    }
 
     #[test]
-    fn test_puzzle_2() {}
+    fn test_puzzle_2() {
+        assert_example::<Puzzle>(YEAR, DAY, "example", 2);
+    }
 }
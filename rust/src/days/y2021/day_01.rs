@@ -1,25 +1,23 @@
 use core::str;
 
-fn load_input(input: &str) -> Vec<u32> {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|d| str::parse(d).unwrap())
-        .collect()
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
+fn load_input(input: &str) -> Result<Vec<u32>, SolveError> {
+    parse_lines(input, |d| Ok(str::parse(d)?))
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let number = load_input(input)
+fn puzzle_1(input: &[u32]) -> Result<Answer, SolveError> {
+    let number = input
         .windows(2)
         .map(|slice| slice[1] > slice[0])
         .filter(|&b| b)
         .count();
 
-    number.to_string()
+    Ok(number.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let sums = load_input(input)
+fn puzzle_2(input: &[u32]) -> Result<Answer, SolveError> {
+    let sums = input
         .windows(3)
         .map(|slice| slice.iter().sum())
         .collect::<Vec<u32>>();
@@ -29,5 +27,23 @@ pub fn puzzle_2(input: &str) -> String {
         .filter(|&b| b)
         .count();
 
-    number.to_string()
+    Ok(number.into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
@@ -1,7 +1,8 @@
 use core::str;
-use std::error::Error;
 
-enum Movement {
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
+pub enum Movement {
     Forward(u32),
     Down(u32),
     Up(u32),
@@ -13,7 +14,7 @@ struct Coord {
     aim: u32,
 }
 
-fn parse_line(line: &str) -> Result<Movement, Box<dyn Error>> {
+fn parse_line(line: &str) -> Result<Movement, SolveError> {
     let (direction, distance) = line.split_once(" ").ok_or("Cannot split line")?;
     let distance = str::parse(distance)?;
     match direction {
@@ -24,16 +25,12 @@ fn parse_line(line: &str) -> Result<Movement, Box<dyn Error>> {
     }
 }
 
-fn load_input(input: &str) -> Vec<Movement> {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| parse_line(line).unwrap())
-        .collect()
+fn load_input(input: &str) -> Result<Vec<Movement>, SolveError> {
+    parse_lines(input, parse_line)
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let coords = load_input(input).iter().fold(
+fn puzzle_1(input: &[Movement]) -> Result<Answer, SolveError> {
+    let coords = input.iter().fold(
         Coord {
             distance: 0,
             depth: 0,
@@ -58,11 +55,11 @@ pub fn puzzle_1(input: &str) -> String {
         },
     );
 
-    { coords.distance * coords.depth }.to_string()
+    Ok({ coords.distance * coords.depth }.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let coords = load_input(input).iter().fold(
+fn puzzle_2(input: &[Movement]) -> Result<Answer, SolveError> {
+    let coords = input.iter().fold(
         Coord {
             distance: 0,
             depth: 0,
@@ -87,5 +84,23 @@ pub fn puzzle_2(input: &str) -> String {
         },
     );
 
-    { coords.distance * coords.depth }.to_string()
+    Ok({ coords.distance * coords.depth }.into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Movement>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
@@ -1,14 +1,12 @@
 use core::str;
 
-fn parse_input(input: &str) -> (u16, Vec<u16>) {
-    let len = input.split_once('\n').unwrap().0.len() as u16;
-    let numbers = input
-        .split('\n')
-        .filter(|l| !l.is_empty())
-        .map(|l| u16::from_str_radix(l, 2).unwrap())
-        .collect();
-
-    (len, numbers)
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
+fn parse_input(input: &str) -> Result<(u16, Vec<u16>), SolveError> {
+    let len = input.lines().next().ok_or("empty input")?.len() as u16;
+    let numbers = parse_lines(input, |l| Ok(u16::from_str_radix(l, 2)?))?;
+
+    Ok((len, numbers))
 }
 
 fn get_most_common_nth_bit(bits: &[u16], k: u16) -> u16 {
@@ -27,50 +25,50 @@ fn get_most_common_nth_bit(bits: &[u16], k: u16) -> u16 {
     }
 }
 
-fn get_least_common_nth_bit(bits: &Vec<u16>, k: u16) -> u16 {
+fn get_least_common_nth_bit(bits: &[u16], k: u16) -> u16 {
     get_most_common_nth_bit(bits, k) ^ 1
 }
 
-fn filter_on_most_common_nth_bit(bits: &Vec<u16>, k: u16) -> Vec<u16> {
+fn filter_on_most_common_nth_bit(bits: &[u16], k: u16) -> Vec<u16> {
     if bits.len() == 1 {
-        return bits.clone();
+        return bits.to_vec();
     }
 
     let mcnb = get_most_common_nth_bit(bits, k);
     bits.iter()
         .filter(|&n| (n & (1 << k)) >> k == mcnb)
-        .map(|&n| n)
+        .copied()
         .collect()
 }
 
-fn filter_on_least_common_nth_bit(bits: &Vec<u16>, k: u16) -> Vec<u16> {
+fn filter_on_least_common_nth_bit(bits: &[u16], k: u16) -> Vec<u16> {
     if bits.len() == 1 {
-        return bits.clone();
+        return bits.to_vec();
     }
 
     let lcnb = get_least_common_nth_bit(bits, k);
     bits.iter()
         .filter(|&n| (n & (1 << k)) >> k == lcnb)
-        .map(|&n| n)
+        .copied()
         .collect()
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let (len, input) = parse_input(input);
+fn puzzle_1(input: &(u16, Vec<u16>)) -> Result<Answer, SolveError> {
+    let (len, input) = (input.0, &input.1);
     let gamma_rate = (0..len)
-        .map(|i| get_most_common_nth_bit(&input, i) * 1 << i)
+        .map(|i| get_most_common_nth_bit(input, i) << i)
         .reduce(|acc, e| acc | e)
-        .unwrap() as u32;
+        .ok_or("empty numbers")? as u32;
     let epsilon_rate = (0..len)
-        .map(|i| get_least_common_nth_bit(&input, i) * 1 << i)
+        .map(|i| get_least_common_nth_bit(input, i) << i)
         .reduce(|acc, e| acc | e)
-        .unwrap() as u32;
+        .ok_or("empty numbers")? as u32;
 
-    (gamma_rate * epsilon_rate).to_string()
+    Ok((gamma_rate * epsilon_rate).into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let (len, input) = parse_input(input);
+fn puzzle_2(input: &(u16, Vec<u16>)) -> Result<Answer, SolveError> {
+    let (len, input) = (input.0, &input.1);
 
     let oo_rating = *(0..len)
         .rev()
@@ -78,44 +76,49 @@ pub fn puzzle_2(input: &str) -> String {
             filter_on_most_common_nth_bit(&acc, n)
         })
         .first()
-        .unwrap() as u32;
+        .ok_or("no oxygen generator rating")? as u32;
 
     let coo_rating = *(0..len)
         .rev()
-        .fold(input, |acc, n| filter_on_least_common_nth_bit(&acc, n))
+        .fold(input.clone(), |acc, n| {
+            filter_on_least_common_nth_bit(&acc, n)
+        })
         .first()
-        .unwrap() as u32;
+        .ok_or("no CO2 scrubber rating")? as u32;
 
-    (oo_rating * coo_rating).to_string()
+    Ok((oo_rating * coo_rating).into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = (u16, Vec<u16>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "00100
-11110
-10110
-10111
-10101
-01111
-00111
-11100
-10000
-11001
-00010
-01010";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_puzzle_1() {
-        let result = puzzle_1(INPUT);
-
-        assert_eq!(result, "198");
+        assert_example::<Puzzle>(2021, 3, "example", 1);
     }
     #[test]
     fn test_puzzle_2() {
-        let result = puzzle_2(INPUT);
-
-        assert_eq!(result, "230");
+        assert_example::<Puzzle>(2021, 3, "example", 2);
     }
 }
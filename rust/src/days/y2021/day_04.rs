@@ -1,56 +1,47 @@
+use crate::utils::{parse_blocks, parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
 type Grid = [[u8; 5]; 5];
 
-fn parse_numbers(line: &str) -> Vec<u8> {
+fn parse_numbers(line: &str) -> Result<Vec<u8>, SolveError> {
     line.split(',')
         .filter(|n| !n.is_empty())
-        .map(|n| n.parse().unwrap())
+        .map(|n| Ok(n.parse()?))
         .collect()
 }
 
-fn parse_grid(grid: &str) -> Grid {
-    grid.split('\n')
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            l.split(' ')
-                .filter(|n| !n.is_empty())
-                .map(|n| n.parse().unwrap())
-                .collect::<Vec<_>>()
-                .try_into()
-                .unwrap()
-        })
-        .collect::<Vec<_>>()
-        .try_into()
-        .unwrap()
+fn parse_grid(grid: &str) -> Result<Grid, SolveError> {
+    parse_lines(grid, |l| {
+        l.split(' ')
+            .filter(|n| !n.is_empty())
+            .map(|n| Ok(n.parse()?))
+            .collect::<Result<Vec<_>, SolveError>>()?
+            .try_into()
+            .map_err(|_| SolveError::new("expected 5 numbers per row"))
+    })?
+    .try_into()
+    .map_err(|_| SolveError::new("expected 5 rows per grid"))
 }
 
-fn parse_input(input: &str) -> (Vec<u8>, Vec<Grid>) {
-    let mut split = input.split("\n\n");
-    let numbers = parse_numbers(split.next().unwrap());
-    let grid = split
-        .filter(|s| !s.is_empty())
-        .map(|lines| parse_grid(lines))
-        .collect();
+fn parse_input(input: &str) -> Result<(Vec<u8>, Vec<Grid>), SolveError> {
+    let (numbers, grids) = input.split_once("\n\n").ok_or("missing grids")?;
+    let numbers = parse_numbers(numbers).map_err(|e| e.at_line(1, numbers))?;
+    let grids = parse_blocks(grids, parse_grid).map_err(|e| e.shift_line(2))?;
 
-    (numbers, grid)
+    Ok((numbers, grids))
 }
 
 fn is_winning(grid: &Grid) -> bool {
-    for l in 0..5 {
+    (0..5).any(|l| {
         let row_wins = grid[l].iter().all(|&n| n == 0);
-        let col_win = (0..5).all(|c| grid[c][l] == 0);
-        if row_wins || col_win {
-            return true;
-        }
-    }
-    return false;
+        let col_win = grid.iter().all(|row| row[l] == 0);
+        row_wins || col_win
+    })
 }
 
 fn replace_number(grid: &mut Grid, number: u8) {
-    for l in 0..5 {
-        for c in 0..5 {
-            if grid[l][c] == number {
-                grid[l][c] = 0;
-            }
+    for cell in grid.iter_mut().flatten() {
+        if *cell == number {
+            *cell = 0;
         }
     }
 }
@@ -63,26 +54,26 @@ fn get_grid_score(grid: &Grid, number: u32) -> u32 {
             .sum::<u32>())
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let (numbers, mut grids) = parse_input(input);
+fn puzzle_1(input: &(Vec<u8>, Vec<Grid>)) -> Result<Answer, SolveError> {
+    let (numbers, mut grids) = input.clone();
 
     let mut winning_grid: Option<(u8, Grid)> = None;
     'o: for n in numbers {
         for grid in grids.iter_mut() {
             replace_number(grid, n);
             if is_winning(grid) {
-                winning_grid = Some((n, grid.clone()));
+                winning_grid = Some((n, *grid));
                 break 'o;
             }
         }
     }
 
-    let (n, grid) = winning_grid.unwrap();
-    get_grid_score(&grid, n as u32).to_string()
+    let (n, grid) = winning_grid.ok_or("no grid wins")?;
+    Ok(get_grid_score(&grid, n as u32).into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let (numbers, mut grids) = parse_input(input);
+fn puzzle_2(input: &(Vec<u8>, Vec<Grid>)) -> Result<Answer, SolveError> {
+    let (numbers, mut grids) = input.clone();
 
     let mut winning_grid: Option<(u8, Grid)> = None;
     for n in numbers {
@@ -92,45 +83,45 @@ pub fn puzzle_2(input: &str) -> String {
             }
             replace_number(grid, n);
             if is_winning(grid) {
-                winning_grid = Some((n, grid.clone()));
+                winning_grid = Some((n, *grid));
             }
         }
     }
 
-    let (n, grid) = winning_grid.unwrap();
-    get_grid_score(&grid, n as u32).to_string()
+    let (n, grid) = winning_grid.ok_or("no grid wins")?;
+    Ok(get_grid_score(&grid, n as u32).into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = (Vec<u8>, Vec<Grid>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
-
-22 13 17 11  0
- 8  2 23  4 24
-21  9 14 16  7
- 6 10  3 18  5
- 1 12 20 15 19
-
- 3 15  0  2 22
- 9 18 13 17  5
-19  8  7 25 23
-20 11 10 24  4
-14 21 16 12  6
-
-14 21 17 24  4
-10 16 15  9 19
-18  8 23 26 20
-22 11 13  6  5
- 2  0 12  3  7";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "4512");
+        assert_example::<Puzzle>(2021, 4, "example", 1);
     }
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "1924");
+        assert_example::<Puzzle>(2021, 4, "example", 2);
     }
 }
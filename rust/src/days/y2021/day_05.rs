@@ -1,39 +1,35 @@
 use std::collections::BTreeMap;
 
-use crate::utils::geometry::{Line2, Point2};
+use crate::utils::{
+    geometry::{Line2, Point2},
+    parse_lines, Answer, PuzzleContext, Solution, SolveError,
+};
 
 type Point = Point2<i16>;
 type Line = Line2<i16>;
 
-fn parse_input(input: &str) -> Vec<Line> {
-    input
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|line| {
-            let (a_str, b_str) = line.split_once(" -> ").unwrap();
-            Line {
-                start: {
-                    let (x, y) = a_str.split_once(',').unwrap();
-                    let x = x.parse().unwrap();
-                    let y = y.parse().unwrap();
-                    Point { x, y }
-                },
-                end: {
-                    let (x, y) = b_str.split_once(',').unwrap();
-                    let x = x.parse().unwrap();
-                    let y = y.parse().unwrap();
-                    Point { x, y }
-                },
-            }
+fn parse_point(input: &str) -> Result<Point, SolveError> {
+    let (x, y) = input.split_once(',').ok_or("expected a point as x,y")?;
+    let x = x.parse()?;
+    let y = y.parse()?;
+    Ok(Point { x, y })
+}
+
+fn parse_input(input: &str) -> Result<Vec<Line>, SolveError> {
+    parse_lines(input, |line| {
+        let (a_str, b_str) = line.split_once(" -> ").ok_or("expected two points")?;
+        Ok(Line {
+            start: parse_point(a_str)?,
+            end: parse_point(b_str)?,
         })
-        .collect()
+    })
 }
 
 fn is_aligned_with_grid(line: &Line) -> bool {
     line.start.x == line.end.x || line.start.y == line.end.y
 }
 
-fn intersect_map(input: &Vec<Line>) -> BTreeMap<Point, usize> {
+fn intersect_map(input: &[Line]) -> BTreeMap<Point, usize> {
     let mut res = BTreeMap::new();
     for line in input {
         for p in line.into_iter() {
@@ -44,49 +40,50 @@ fn intersect_map(input: &Vec<Line>) -> BTreeMap<Point, usize> {
     res
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let input = parse_input(input)
-        .into_iter()
-        .filter(is_aligned_with_grid)
-        .collect();
+fn puzzle_1(input: &[Line]) -> Result<Answer, SolveError> {
+    let input = input
+        .iter()
+        .filter(|line| is_aligned_with_grid(line))
+        .cloned()
+        .collect::<Vec<_>>();
     let intersections = intersect_map(&input);
 
-    intersections
-        .iter()
-        .filter(|(_, &c)| c > 1)
-        .count()
-        .to_string()
+    Ok(intersections.iter().filter(|(_, &c)| c > 1).count().into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let input = parse_input(input);
-    let intersections = intersect_map(&input);
+fn puzzle_2(input: &[Line]) -> Result<Answer, SolveError> {
+    let intersections = intersect_map(input);
 
-    intersections
-        .iter()
-        .filter(|(_, &c)| c > 1)
-        .count()
-        .to_string()
+    Ok(intersections.iter().filter(|(_, &c)| c > 1).count().into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Line>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "0,9 -> 5,9
-8,0 -> 0,8
-9,4 -> 3,4
-2,2 -> 2,1
-7,0 -> 7,4
-6,4 -> 2,0
-0,9 -> 2,9
-3,4 -> 1,4
-0,0 -> 8,8
-5,5 -> 8,2";
+    use crate::utils::fixtures::{assert_example, example_input};
 
     use super::*;
 
     #[test]
     fn test_can_parse_input() {
-        let parsed = parse_input(INPUT);
+        let parsed = parse_input(example_input(2021, 5, "example")).unwrap();
         assert_eq!(
             parsed.first(),
             Some(&Line {
@@ -99,11 +96,11 @@ mod tests {
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "5");
+        assert_example::<Puzzle>(2021, 5, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "12");
+        assert_example::<Puzzle>(2021, 5, "example", 2);
     }
 }
@@ -1,40 +1,53 @@
-fn load_input_data(input: &str) -> Vec<Vec<u32>> {
-    let elf_day_calories = input.split("\n\n");
-    elf_day_calories
-        .map(|subdata| -> Vec<u32> {
-            subdata
-                .lines()
-                .filter(|x| !x.is_empty())
-                .map(|x| x.parse::<u32>().unwrap())
-                .collect()
-        })
-        .collect()
+use crate::utils::{parse_blocks, parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
+fn load_input_data(input: &str) -> Result<Vec<Vec<u32>>, SolveError> {
+    parse_blocks(input, |subdata| {
+        parse_lines(subdata, |x| Ok(x.parse::<u32>()?))
+    })
 }
 
-fn get_total_calories_by_elf(calories: Vec<Vec<u32>>) -> Vec<u32> {
+fn get_total_calories_by_elf(calories: &[Vec<u32>]) -> Vec<u32> {
     calories
         .iter()
         .map(|day_meal| day_meal.iter().sum())
         .collect()
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let calories = load_input_data(input);
+fn puzzle_1(calories: &[Vec<u32>]) -> Result<Answer, SolveError> {
     let total_calories = get_total_calories_by_elf(calories);
 
     match total_calories.iter().max() {
-        Some(max) => max.to_string(),
-        None => 0.to_string(),
+        Some(max) => Ok(max.into()),
+        None => Ok(0.into()),
     }
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let calories = load_input_data(input);
+fn puzzle_2(calories: &[Vec<u32>]) -> Result<Answer, SolveError> {
     let mut total_calories = get_total_calories_by_elf(calories);
-    assert!(total_calories.len() >= 3);
+    if total_calories.len() < 3 {
+        return Err(SolveError::new("expected at least three elves"));
+    }
 
     total_calories.sort();
 
     let top_three = &total_calories[total_calories.len() - 3..];
-    top_three.iter().sum::<u32>().to_string()
+    Ok(top_three.iter().sum::<u32>().into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Vec<u32>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input_data(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
@@ -1,8 +1,9 @@
-use core::panic;
 use std::cmp::{Ord, Ordering, PartialEq};
 
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
 #[derive(Debug, PartialEq, Eq, Clone)]
-enum Move {
+pub enum Move {
     Rock,
     Paper,
     Scissors,
@@ -86,41 +87,46 @@ impl Hand {
     }
 }
 
-fn parse_hand_from_first_case(line: &str) -> Hand {
-    let (them_move, us_move) = line.split_once(' ').unwrap();
-
-    let us_move = match us_move {
-        "X" => Move::Rock,
-        "Y" => Move::Paper,
-        "Z" => Move::Scissors,
-        _ => panic!("Failed to parse the us move"),
-    };
+// The meaning of the second column depends on the puzzle, it is kept as is
+fn parse_line(line: &str) -> Result<(Move, char), SolveError> {
+    let (them_move, column) = line.split_once(' ').ok_or("expected two columns")?;
 
     let them_move = match them_move {
         "A" => Move::Rock,
         "B" => Move::Paper,
         "C" => Move::Scissors,
-        _ => panic!("Failed to parse the them move"),
+        _ => return Err(SolveError::new("failed to parse the them move")),
+    };
+    let column = match column {
+        "X" | "Y" | "Z" => column.chars().next().unwrap(),
+        _ => return Err(SolveError::new("failed to parse the second column")),
     };
 
-    Hand { us_move, them_move }
+    Ok((them_move, column))
 }
 
-fn parse_hand_from_second_case(line: &str) -> Hand {
-    let (them_move, expected_outcome) = line.split_once(' ').unwrap();
-
-    let expected_outcome = match expected_outcome {
-        "X" => Outcome::Loose,
-        "Y" => Outcome::Draw,
-        "Z" => Outcome::Win,
-        _ => panic!("Failed to parse the expected outcome"),
+fn parse_hand_from_first_case((them_move, column): &(Move, char)) -> Hand {
+    let us_move = match column {
+        'X' => Move::Rock,
+        'Y' => Move::Paper,
+        'Z' => Move::Scissors,
+        _ => unreachable!(),
     };
 
-    let them_move = match them_move {
-        "A" => Move::Rock,
-        "B" => Move::Paper,
-        "C" => Move::Scissors,
-        _ => panic!("Failed to parse the them move"),
+    Hand {
+        us_move,
+        them_move: them_move.clone(),
+    }
+}
+
+fn parse_hand_from_second_case((them_move, column): &(Move, char)) -> Hand {
+    let them_move = them_move.clone();
+
+    let expected_outcome = match column {
+        'X' => Outcome::Loose,
+        'Y' => Outcome::Draw,
+        'Z' => Outcome::Win,
+        _ => unreachable!(),
     };
 
     let us_move = match expected_outcome {
@@ -140,36 +146,52 @@ fn parse_hand_from_second_case(line: &str) -> Hand {
     Hand { us_move, them_move }
 }
 
-fn get_hands_from_input_first_case(input: &str) -> Vec<Hand> {
-    input
-        .lines()
-        .map(|line| parse_hand_from_first_case(line))
-        .collect()
+fn parse_input(input: &str) -> Result<Vec<(Move, char)>, SolveError> {
+    parse_lines(input, parse_line)
+}
+
+fn get_hands_from_input_first_case(input: &[(Move, char)]) -> Vec<Hand> {
+    input.iter().map(parse_hand_from_first_case).collect()
 }
 
-fn get_hands_from_input_second_case(input: &str) -> Vec<Hand> {
-    input
-        .lines()
-        .map(|line| parse_hand_from_second_case(line))
-        .collect()
+fn get_hands_from_input_second_case(input: &[(Move, char)]) -> Vec<Hand> {
+    input.iter().map(parse_hand_from_second_case).collect()
 }
 
 fn get_scores(hands: Vec<Hand>) -> Vec<u32> {
     hands.iter().map(|hand| hand.get_score()).collect()
 }
 
-pub fn puzzle_1(input: &str) -> String {
+fn puzzle_1(input: &[(Move, char)]) -> Result<Answer, SolveError> {
     let hands = get_hands_from_input_first_case(input);
     let scores = get_scores(hands);
 
-    scores.iter().sum::<u32>().to_string()
+    Ok(scores.iter().sum::<u32>().into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
+fn puzzle_2(input: &[(Move, char)]) -> Result<Answer, SolveError> {
     let hands = get_hands_from_input_second_case(input);
     let scores = get_scores(hands);
 
-    scores.iter().sum::<u32>().to_string()
+    Ok(scores.iter().sum::<u32>().into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<(Move, char)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
@@ -179,7 +201,7 @@ mod tests {
     #[test]
     fn parse_hand_case_1() {
         let hand_str = "A Y";
-        let hand = parse_hand_from_first_case(hand_str);
+        let hand = parse_hand_from_first_case(&parse_line(hand_str).unwrap());
 
         assert_eq!(hand.them_move, Move::Rock);
         assert_eq!(hand.us_move, Move::Paper);
@@ -189,7 +211,7 @@ mod tests {
     #[test]
     fn parse_hand_case_2() {
         let hand_str = "A Y";
-        let hand = parse_hand_from_second_case(hand_str);
+        let hand = parse_hand_from_second_case(&parse_line(hand_str).unwrap());
 
         assert_eq!(hand.them_move, Move::Rock);
         assert_eq!(hand.us_move, Move::Rock);
@@ -1,32 +1,43 @@
 use std::collections::HashSet;
 
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
 const PRIORITIES: &str = "_abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
 fn get_priorities(c: char) -> usize {
     PRIORITIES.find(c).unwrap()
 }
 
-fn get_shared_letter(first: &str, second: &str) -> char {
+fn get_shared_letter(first: &str, second: &str) -> Option<char> {
     let char_set_first: HashSet<char> = HashSet::from_iter(first.chars());
     let char_set_second: HashSet<char> = HashSet::from_iter(second.chars());
 
     let mut inter = char_set_first.intersection(&char_set_second);
-    *inter.next().unwrap()
+    inter.next().copied()
+}
+
+fn parse_input(input: &str) -> Result<Vec<String>, SolveError> {
+    parse_lines(input, |line| {
+        match line.chars().all(|c| c.is_ascii_alphabetic()) {
+            true => Ok(line.to_string()),
+            false => Err(SolveError::new("expected only letters")),
+        }
+    })
 }
 
-fn get_shared_letters(input: &str) -> Vec<char> {
+fn get_shared_letters(input: &[String]) -> Result<Vec<char>, SolveError> {
     input
-        .split('\n')
-        .filter(|x| !x.is_empty())
+        .iter()
         .map(|letters| {
             let l = letters.len();
             let (first, second) = letters.split_at(l / 2);
             get_shared_letter(first, second)
+                .ok_or_else(|| SolveError::new(format!("no shared letter in {}", letters)))
         })
         .collect()
 }
 
-fn get_group_shared_letter(chunk: &[&str]) -> Option<char> {
+fn get_group_shared_letter(chunk: &[String]) -> Option<char> {
     for letter in chunk.first().unwrap().chars() {
         let in_all_bags = chunk.iter().all(|letters| letters.contains(letter));
         if in_all_bags {
@@ -36,38 +47,51 @@ fn get_group_shared_letter(chunk: &[&str]) -> Option<char> {
     None
 }
 
-fn get_group_letters(input: &str) -> Vec<char> {
+fn get_group_letters(input: &[String]) -> Result<Vec<char>, SolveError> {
     input
-        .split('\n')
-        .filter(|x| !x.is_empty())
-        .collect::<Vec<_>>()
         .chunks(3)
-        .map(|chunk| get_group_shared_letter(chunk).unwrap())
+        .map(|chunk| {
+            get_group_shared_letter(chunk)
+                .ok_or_else(|| SolveError::new(format!("no shared letter in group {}", chunk[0])))
+        })
         .collect()
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let shared_letters = get_shared_letters(input);
+fn puzzle_1(input: &[String]) -> Result<Answer, SolveError> {
+    let shared_letters = get_shared_letters(input)?;
 
     let sum: usize = shared_letters.iter().map(|x| get_priorities(*x)).sum();
-    sum.to_string()
+    Ok(sum.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let group_letters = get_group_letters(input);
+fn puzzle_2(input: &[String]) -> Result<Answer, SolveError> {
+    let group_letters = get_group_letters(input)?;
 
     let sum: usize = group_letters.iter().map(|x| get_priorities(*x)).sum();
-    sum.to_string()
+    Ok(sum.into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw";
+    use crate::utils::fixtures::{assert_example, example_input};
 
     use super::*;
 
@@ -79,28 +103,24 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
 
     #[test]
     fn test_get_shared_letter() {
-        assert_eq!(get_shared_letter("vJrwpWtwJgWr", "hcsFMMfFFhFp"), 'p');
+        assert_eq!(get_shared_letter("vJrwpWtwJgWr", "hcsFMMfFFhFp"), Some('p'));
     }
 
     #[test]
     fn test_get_shared_letters() {
         assert_eq!(
-            get_shared_letters(INPUT),
+            get_shared_letters(&parse_input(example_input(2022, 3, "example")).unwrap()).unwrap(),
             vec!['p', 'L', 'P', 'v', 't', 's']
         );
     }
 
     #[test]
     fn test_puzzle_1() {
-        let result = puzzle_1(INPUT);
-
-        assert_eq!(result, "157");
+        assert_example::<Puzzle>(2022, 3, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        let result = puzzle_2(INPUT);
-
-        assert_eq!(result, "70");
+        assert_example::<Puzzle>(2022, 3, "example", 2);
     }
 }
@@ -1,5 +1,7 @@
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
 #[derive(PartialEq, Eq, Debug)]
-struct Job {
+pub struct Job {
     start: u32,
     end: u32,
 }
@@ -35,44 +37,57 @@ impl TryFrom<&str> for Job {
     }
 }
 
-fn parse_pair(input: &str) -> (Job, Job) {
-    let pair = input.split_once(',').unwrap();
-    let first = Job::try_from(pair.0).unwrap();
-    let second = Job::try_from(pair.1).unwrap();
+fn parse_pair(input: &str) -> Result<(Job, Job), SolveError> {
+    let pair = input.split_once(',').ok_or("expected two jobs")?;
+    let first = Job::try_from(pair.0).map_err(|_| "failed to parse the first job")?;
+    let second = Job::try_from(pair.1).map_err(|_| "failed to parse the second job")?;
+
+    Ok((first, second))
+}
 
-    (first, second)
+fn parse_input(input: &str) -> Result<Vec<(Job, Job)>, SolveError> {
+    parse_lines(input, parse_pair)
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    input
-        .split('\n')
-        .filter(|x| !x.is_empty())
-        .map(parse_pair)
-        .map(|(pa, pb)| pa.includes(&pb) || pb.includes(&pa))
+fn puzzle_1(input: &[(Job, Job)]) -> Result<Answer, SolveError> {
+    Ok(input
+        .iter()
+        .map(|(pa, pb)| pa.includes(pb) || pb.includes(pa))
         .filter(|x| *x)
         .count()
-        .to_string()
+        .into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    input
-        .split('\n')
-        .filter(|x| !x.is_empty())
-        .map(parse_pair)
-        .map(|(pa, pb)| pa.overlaps(&pb) || pb.overlaps(&pa))
+fn puzzle_2(input: &[(Job, Job)]) -> Result<Answer, SolveError> {
+    Ok(input
+        .iter()
+        .map(|(pa, pb)| pa.overlaps(pb) || pb.overlaps(pa))
         .filter(|x| *x)
         .count()
-        .to_string()
+        .into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<(Job, Job)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "2-4,6-8
-2-3,4-5
-5-7,7-9
-2-8,3-7
-6-6,4-6
-2-6,4-8";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
@@ -87,17 +102,17 @@ mod tests {
         let pair = "2-4,6-8";
         assert_eq!(
             parse_pair(pair),
-            (Job { start: 2, end: 4 }, Job { start: 6, end: 8 })
+            Ok((Job { start: 2, end: 4 }, Job { start: 6, end: 8 }))
         );
     }
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "2");
+        assert_example::<Puzzle>(2022, 4, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "4");
+        assert_example::<Puzzle>(2022, 4, "example", 2);
     }
 }
@@ -1,7 +1,9 @@
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
 type Piles = Vec<Vec<char>>;
 
-#[derive(Debug, PartialEq, Eq)]
-struct Step {
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Step {
     number: u32,
     from: u32,
     to: u32,
@@ -57,44 +59,53 @@ fn parse_piles(input: &str) -> Piles {
     piles
 }
 
-fn parse_steps(input: &str) -> Vec<Step> {
-    input
-        .split('\n')
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let (number_str, rest_str) = line.split_once("from").unwrap();
-            let (from_str, to_str) = rest_str.split_once("to").unwrap();
-            let number = number_str.replace("move", "").trim().parse().unwrap();
-            let from = from_str.trim().parse().unwrap();
-            let to = to_str.trim().parse().unwrap();
-            Step { number, from, to }
-        })
-        .collect()
+fn parse_steps(input: &str) -> Result<Vec<Step>, SolveError> {
+    parse_lines(input, |line| {
+        let (number_str, rest_str) = line.split_once("from").ok_or("expected a from pile")?;
+        let (from_str, to_str) = rest_str.split_once("to").ok_or("expected a to pile")?;
+        let number = number_str.replace("move", "").trim().parse()?;
+        let from = from_str.trim().parse()?;
+        let to = to_str.trim().parse()?;
+        Ok(Step { number, from, to })
+    })
 }
 
 impl Step {
-    fn perform_move(&self, piles: &mut Piles, crane_type: Crane) {
+    fn perform_move(&self, piles: &mut Piles, crane_type: Crane) -> Result<(), SolveError> {
+        let pile_error = || SolveError::new(format!("cannot perform {:?}", self));
         for _ in 0..self.number {
-            let pile_from = piles.get_mut(self.from as usize - 1).unwrap();
+            let pile_from = piles
+                .get_mut((self.from as usize).wrapping_sub(1))
+                .ok_or_else(pile_error)?;
             let mut to_move = match crane_type {
-                Crane::Crane9000 => vec![pile_from.pop().unwrap()],
-                Crane::Crane9001 => pile_from.split_off(pile_from.len() - self.number as usize),
+                Crane::Crane9000 => vec![pile_from.pop().ok_or_else(pile_error)?],
+                Crane::Crane9001 => {
+                    let at = pile_from
+                        .len()
+                        .checked_sub(self.number as usize)
+                        .ok_or_else(pile_error)?;
+                    pile_from.split_off(at)
+                }
             };
-            let pile_to = piles.get_mut(self.to as usize - 1).unwrap();
+            let pile_to = piles
+                .get_mut((self.to as usize).wrapping_sub(1))
+                .ok_or_else(pile_error)?;
             match crane_type {
-                Crane::Crane9000 => pile_to.push(*to_move.first().unwrap()),
+                Crane::Crane9000 => pile_to.push(to_move[0]),
                 Crane::Crane9001 => {
                     pile_to.append(&mut to_move);
                     break;
                 }
             }
         }
+        Ok(())
     }
 }
 
-fn parse_input(input: &str) -> (Piles, Vec<Step>) {
-    let (piles, steps) = input.split_once("\n\n").unwrap();
-    (parse_piles(piles), parse_steps(steps))
+fn parse_input(input: &str) -> Result<(Piles, Vec<Step>), SolveError> {
+    let (piles, steps) = input.split_once("\n\n").ok_or("missing steps")?;
+    let steps = parse_steps(steps).map_err(|e| e.shift_line(piles.lines().count() + 1))?;
+    Ok((parse_piles(piles), steps))
 }
 
 pub fn get_result(piles: &Piles) -> String {
@@ -108,41 +119,47 @@ pub fn get_result(piles: &Piles) -> String {
     result
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let parsed = parse_input(input);
-    let steps = parsed.1;
-    let mut piles = parsed.0;
+fn puzzle_1(input: &(Piles, Vec<Step>)) -> Result<Answer, SolveError> {
+    let (mut piles, steps) = input.clone();
 
-    steps
-        .into_iter()
-        .for_each(|step| step.perform_move(&mut piles, Crane::Crane9000));
+    for step in steps {
+        step.perform_move(&mut piles, Crane::Crane9000)?;
+    }
 
-    get_result(&piles)
+    Ok(get_result(&piles).into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let parsed = parse_input(input);
-    let steps = parsed.1;
-    let mut piles = parsed.0;
+fn puzzle_2(input: &(Piles, Vec<Step>)) -> Result<Answer, SolveError> {
+    let (mut piles, steps) = input.clone();
 
-    steps
-        .into_iter()
-        .for_each(|step| step.perform_move(&mut piles, Crane::Crane9001));
+    for step in steps {
+        step.perform_move(&mut piles, Crane::Crane9001)?;
+    }
 
-    get_result(&piles)
+    Ok(get_result(&piles).into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = (Piles, Vec<Step>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "    [D]    
-[N] [C]    
-[Z] [M] [P]
- 1   2   3 
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
@@ -164,7 +181,7 @@ move 1 from 1 to 2";
     #[test]
     fn test_parse_step() {
         let step_str = "move 3 from 1 to 3";
-        let steps = parse_steps(step_str);
+        let steps = parse_steps(step_str).unwrap();
         assert_eq!(
             steps,
             vec![Step {
@@ -177,15 +194,11 @@ move 1 from 1 to 2";
 
     #[test]
     fn test_puzzle_1() {
-        let result = puzzle_1(INPUT);
-
-        assert_eq!(result, "CMZ");
+        assert_example::<Puzzle>(2022, 5, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        let result = puzzle_2(INPUT);
-
-        assert_eq!(result, "MCD");
+        assert_example::<Puzzle>(2022, 5, "example", 2);
     }
 }
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use crate::utils::{Answer, PuzzleContext, Solution, SolveError};
+
 fn no_duplicate(window: &str) -> bool {
     HashSet::<char>::from_iter(window.chars()).len() == window.len()
 }
@@ -21,20 +23,45 @@ fn get_start_parker_pos(string: &str, window_size: usize) -> Option<(usize, &str
     None
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let pos = get_start_parker_pos(input.trim(), 4).unwrap().0 + 1;
-    pos.to_string()
+fn parse_input(input: &str) -> Result<String, SolveError> {
+    let input = input.trim();
+    match input.is_ascii() {
+        true => Ok(input.to_string()),
+        false => Err(SolveError::new("expected an ascii datastream")),
+    }
+}
+
+fn puzzle_1(input: &str) -> Result<Answer, SolveError> {
+    let (pos, _) = get_start_parker_pos(input, 4).ok_or("no start of packet marker")?;
+    Ok((pos + 1).into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let pos = get_start_parker_pos(input.trim(), 14).unwrap().0 + 1;
-    pos.to_string()
+fn puzzle_2(input: &str) -> Result<Answer, SolveError> {
+    let (pos, _) = get_start_parker_pos(input, 14).ok_or("no start of message marker")?;
+    Ok((pos + 1).into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
-    const INPUT: &str = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+    use crate::utils::fixtures::{assert_example, example_input};
 
     use core::panic;
 
@@ -42,7 +69,8 @@ mod tests {
 
     #[test]
     fn test_detect_start() {
-        if let Some((i, string)) = get_start_parker_pos(INPUT, 4) {
+        let input = parse_input(example_input(2022, 6, "example")).unwrap();
+        if let Some((i, string)) = get_start_parker_pos(&input, 4) {
             assert_eq!(i + 1, 11);
             assert_eq!(string, "ljwzlrfnpqdbhtmscgvjw");
         } else {
@@ -52,11 +80,11 @@ mod tests {
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "11");
+        assert_example::<Puzzle>(2022, 6, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "26");
+        assert_example::<Puzzle>(2022, 6, "example", 2);
     }
 }
@@ -3,6 +3,8 @@ use std::{
     rc::{Rc, Weak},
 };
 
+use crate::utils::{Answer, PuzzleContext, Solution, SolveError};
+
 enum PathElement {
     Current,
     Parent,
@@ -130,7 +132,7 @@ impl DirElement {
 }
 
 #[derive(Debug)]
-struct DirCursor {
+pub struct DirCursor {
     cursor: DirElement,
     root: Rc<RefCell<Dir>>,
     is_ls: bool,
@@ -199,9 +201,8 @@ impl DirCursor {
                 }
                 DirElement::Dir(cursor) => {
                     let mut is_file = false;
-                    let size_or_dir = size_or_dir.parse::<u32>().map(|s| {
+                    let size_or_dir = size_or_dir.parse::<u32>().inspect(|_| {
                         is_file = true;
-                        s
                     });
                     if is_file {
                         let size = size_or_dir.unwrap();
@@ -228,13 +229,17 @@ impl DirCursor {
     }
 }
 
-fn parse_output(text: &str) -> DirCursor {
+fn parse_output(text: &str) -> Result<DirCursor, SolveError> {
     let mut dir = DirCursor::create_empty();
-    for line in text.split('\n').map(|l| l.trim()).filter(|l| !l.is_empty()) {
-        dir.handle_line(line).unwrap();
+    for (i, line) in text.split('\n').map(|l| l.trim()).enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        dir.handle_line(line)
+            .map_err(|_| SolveError::new("unexpected terminal line").at_line(i + 1, line))?;
     }
 
-    dir
+    Ok(dir)
 }
 
 fn get_small_dir_recur(dir: &Rc<RefCell<Dir>>, result: &mut Vec<Rc<RefCell<Dir>>>, limit: u32) {
@@ -259,8 +264,7 @@ fn get_all_dir_recur(dir: &Rc<RefCell<Dir>>, result: &mut Vec<Rc<RefCell<Dir>>>)
     }
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let dir = parse_output(input);
+fn puzzle_1(dir: &DirCursor) -> Result<Answer, SolveError> {
     let mut small_dirs = vec![];
     get_small_dir_recur(&dir.root, &mut small_dirs, 100_000);
 
@@ -269,18 +273,18 @@ pub fn puzzle_1(input: &str) -> String {
         .map(|d| d.borrow().get_total_size())
         .sum();
 
-    size.to_string()
+    Ok(size.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
+fn puzzle_2(dir: &DirCursor) -> Result<Answer, SolveError> {
     let target = 30_000_000;
-    let fs_total = 70_000_000;
-
-    let dir = parse_output(input);
+    let fs_total: u32 = 70_000_000;
 
-    let space_left = fs_total - dir.root.borrow().get_total_size();
+    let space_left = fs_total
+        .checked_sub(dir.root.borrow().get_total_size())
+        .ok_or("the files do not fit on the disk")?;
     if space_left > target {
-        return String::new();
+        return Ok(0.into());
     }
     let to_save = target - space_left;
 
@@ -293,60 +297,56 @@ pub fn puzzle_2(input: &str) -> String {
         .filter(|s| *s >= to_save)
         .collect();
     sizes.sort();
-    sizes.first().unwrap().to_string()
+    Ok(sizes.first().ok_or("no directory is large enough")?.into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = DirCursor;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_output(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "$ cd /
-$ ls
-dir a
-14848514 b.txt
-8504156 c.dat
-dir d
-$ cd a
-$ ls
-dir e
-29116 f
-2557 g
-62596 h.lst
-$ cd e
-$ ls
-584 i
-$ cd ..
-$ cd ..
-$ cd d
-$ ls
-4060174 j
-8033020 d.log
-5626152 d.ext
-7214296 k";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_create_empty_dir() {
         let output = "$ cd /";
-        let dir = parse_output(output);
+        let dir = parse_output(output).unwrap();
         assert_eq!(dir.root.borrow().children.len(), 0);
     }
 
     #[test]
     fn test_create_dirs() {
         let output = "$ cd /\n$ ls\ndir a\n12345 b\n";
-        let dir = parse_output(output);
+        let dir = parse_output(output).unwrap();
         assert_eq!(dir.root.borrow().children.len(), 2);
-        assert_eq!(dir.root.borrow().children.get(0).unwrap().get_size(), 0);
+        assert_eq!(dir.root.borrow().children.first().unwrap().get_size(), 0);
         assert_eq!(dir.root.borrow().children.get(1).unwrap().get_size(), 12345);
     }
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "95437");
+        assert_example::<Puzzle>(2022, 7, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "24933642");
+        assert_example::<Puzzle>(2022, 7, "example", 2);
     }
 }
@@ -1,4 +1,4 @@
-use crate::utils::geometry::Point2;
+use crate::utils::{geometry::Point2, parse_lines, Answer, PuzzleContext, Solution, SolveError};
 
 type Point = Point2<usize>;
 type TreeGrid = Vec<Vec<u8>>;
@@ -18,19 +18,19 @@ enum Side {
 //   x
 //      S
 
-fn load_input(input: &str) -> Result<TreeGrid, ()> {
-    let output: Vec<Vec<u8>> = input
-        .split('\n')
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).map(|d| d.try_into().unwrap()).unwrap())
-                .collect()
-        })
-        .collect();
-
-    if !output.iter().map(|l| l.len()).all(|x| x == output.len()) {
-        return Err(());
+fn load_input(input: &str) -> Result<TreeGrid, SolveError> {
+    let output: Vec<Vec<u8>> = parse_lines(input, |line| {
+        line.chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .map(|d| d as u8)
+                    .ok_or_else(|| SolveError::new(format!("invalid tree height {}", c)))
+            })
+            .collect()
+    })?;
+
+    if output.is_empty() || !output.iter().map(|l| l.len()).all(|x| x == output.len()) {
+        return Err(SolveError::new("expected a square grid of trees"));
     }
 
     Ok(output)
@@ -127,8 +127,7 @@ fn get_visibility_length(forest: &TreeGrid, side: Side, pos: Point) -> usize {
 fn is_tree_visible(forest: &TreeGrid, pos: Point) -> bool {
     [Side::North, Side::West, Side::South, Side::East]
         .iter()
-        .map(|side| is_visible_from_side(forest, *side, pos))
-        .any(|x| x)
+        .any(|side| is_visible_from_side(forest, *side, pos))
 }
 
 fn get_visible_trees(input: &TreeGrid) -> Vec<Vec<bool>> {
@@ -158,9 +157,8 @@ fn get_visibility_scores(input: &TreeGrid) -> Vec<Vec<usize>> {
     output
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let parsed_input = load_input(input).unwrap();
-    let visible_trees = get_visible_trees(&parsed_input);
+fn puzzle_1(input: &TreeGrid) -> Result<Answer, SolveError> {
+    let visible_trees = get_visible_trees(input);
 
     let total: usize = visible_trees
         .iter()
@@ -175,28 +173,41 @@ pub fn puzzle_1(input: &str) -> String {
         })
         .sum();
 
-    total.to_string()
+    Ok(total.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let parsed_input = load_input(input).unwrap();
-    let visibility_scores = get_visibility_scores(&parsed_input);
+fn puzzle_2(input: &TreeGrid) -> Result<Answer, SolveError> {
+    let visibility_scores = get_visibility_scores(input);
 
-    visibility_scores
+    Ok(visibility_scores
         .iter()
         .map(|v| v.iter().max().unwrap())
         .max()
         .unwrap()
-        .to_string()
+        .into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = TreeGrid;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "30373
-25512
-65332
-33549
-35390";
+    use crate::utils::fixtures::{assert_example, example_input};
 
     use super::*;
 
@@ -209,7 +220,7 @@ mod tests {
 
     #[test]
     fn test_is_tree_visible() {
-        let forest = load_input(INPUT).unwrap();
+        let forest = load_input(example_input(2022, 8, "example")).unwrap();
 
         assert!(is_tree_visible(&forest, Point { x: 0, y: 0 }));
         assert!(is_tree_visible(&forest, Point { x: 1, y: 1 }));
@@ -235,11 +246,11 @@ mod tests {
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "21");
+        assert_example::<Puzzle>(2022, 8, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "8");
+        assert_example::<Puzzle>(2022, 8, "example", 2);
     }
 }
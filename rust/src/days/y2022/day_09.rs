@@ -1,6 +1,11 @@
-use std::collections::BTreeSet;
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display},
+};
 
-use crate::utils::geometry::Point2;
+use crate::utils::{
+    geometry::Point2, parse_lines, Answer, Log, PuzzleContext, Solution, SolveError,
+};
 
 type Point = Point2<i32>;
 type Vector = Point2<i32>;
@@ -17,7 +22,7 @@ impl Rope {
     }
 
     fn get_tail(&self) -> Option<&Point> {
-        return self.knots.last();
+        self.knots.last()
     }
 
     fn move_head(&mut self, vector: Vector) -> Vec<Point> {
@@ -53,41 +58,50 @@ impl Rope {
     }
 }
 
-#[allow(dead_code)]
-fn print_debug_pos(input: &BTreeSet<Point>) {
-    let mut max_x = 0;
-    let mut max_y = 0;
-    let mut min_x = 0;
-    let mut min_y = 0;
+// The visited positions as a grid, `s` being the start, only drawn when the log is at the debug level
+struct DebugPos<'a>(&'a BTreeSet<Point>);
 
-    input.iter().for_each(|Point { x, y }| {
-        if x < &min_x {
-            min_x = *x;
-        }
-        if y < &min_y {
-            min_y = *y;
-        }
-        if x > &max_x {
-            max_x = *x;
-        }
-        if y > &max_y {
-            max_y = *y;
-        }
-    });
-
-    for y in (min_y..=max_y).rev() {
-        let mut line = String::new();
-        line.push_str(&format!("{}\t", y));
-        for x in min_x..=max_x {
-            if x == 0 && y == 0 {
-                line.push('s');
-            } else if input.contains(&Point { x, y }) {
-                line.push('#');
-            } else {
-                line.push('.');
+impl Display for DebugPos<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let input = self.0;
+        let mut max_x = 0;
+        let mut max_y = 0;
+        let mut min_x = 0;
+        let mut min_y = 0;
+
+        input.iter().for_each(|Point { x, y }| {
+            if x < &min_x {
+                min_x = *x;
+            }
+            if y < &min_y {
+                min_y = *y;
+            }
+            if x > &max_x {
+                max_x = *x;
+            }
+            if y > &max_y {
+                max_y = *y;
+            }
+        });
+
+        for y in (min_y..=max_y).rev() {
+            let mut line = String::new();
+            line.push_str(&format!("{}\t", y));
+            for x in min_x..=max_x {
+                if x == 0 && y == 0 {
+                    line.push('s');
+                } else if input.contains(&Point { x, y }) {
+                    line.push('#');
+                } else {
+                    line.push('.');
+                }
+            }
+            if y != max_y {
+                writeln!(f)?;
             }
+            write!(f, "{line}")?;
         }
-        println!("{line}");
+        Ok(())
     }
 }
 
@@ -101,25 +115,23 @@ fn parse_direction(value: char) -> Result<Point, ()> {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Vector> {
-    input
-        .split('\n')
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            let (direction, distance) = l.split_once(' ').unwrap();
-            let distance = distance.parse().ok().unwrap();
-            let vector = direction
-                .chars()
-                .next()
-                .and_then(|c| parse_direction(c).ok())
-                .unwrap()
-                * distance;
-            vector
-        })
-        .collect()
+fn parse_input(input: &str) -> Result<Vec<Vector>, SolveError> {
+    parse_lines(input, |l| {
+        let (direction, distance) = l
+            .split_once(' ')
+            .ok_or("expected a direction and a distance")?;
+        let distance: i32 = distance.parse()?;
+        let vector = direction
+            .chars()
+            .next()
+            .and_then(|c| parse_direction(c).ok())
+            .ok_or("unknown direction")?
+            * distance;
+        Ok(vector)
+    })
 }
 
-fn get_tail_pos(mouvements: &Vec<Vector>, rope: &mut Rope) -> BTreeSet<Point> {
+fn get_tail_pos(mouvements: &[Vector], rope: &mut Rope, log: &Log) -> BTreeSet<Point> {
     let mut positions = BTreeSet::new();
     positions.insert(*rope.get_tail().unwrap());
 
@@ -127,40 +139,47 @@ fn get_tail_pos(mouvements: &Vec<Vector>, rope: &mut Rope) -> BTreeSet<Point> {
         for pos_tail in rope.move_head(*vector) {
             positions.insert(pos_tail);
         }
-        // print_debug_pos(&positions);
+        log.artifact("positions", DebugPos(&positions));
     }
 
     positions
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let mouvements = parse_input(input);
-
+fn puzzle_1(mouvements: &[Vector], log: &Log) -> Result<Answer, SolveError> {
     let mut rope = Rope::new(2, Point { x: 0, y: 0 });
 
-    let positions = get_tail_pos(&mouvements, &mut rope);
-    positions.len().to_string()
+    let positions = get_tail_pos(mouvements, &mut rope, log);
+    Ok(positions.len().into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let mouvements = parse_input(input);
-
+fn puzzle_2(mouvements: &[Vector], log: &Log) -> Result<Answer, SolveError> {
     let mut rope = Rope::new(10, Point { x: 0, y: 0 });
 
-    let positions = get_tail_pos(&mouvements, &mut rope);
-    positions.len().to_string()
+    let positions = get_tail_pos(mouvements, &mut rope, log);
+    Ok(positions.len().into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Vector>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input, context.log())
+    }
+
+    fn part_2(input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input, context.log())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "R 5
-U 8
-L 8
-D 3
-R 17
-D 10
-L 25
-U 20";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
@@ -179,17 +198,17 @@ U 20";
         let input = "R 4\nU 4\n";
         assert_eq!(
             parse_input(input),
-            vec![Vector::unit_x() * 4, Vector::unit_y() * 4]
+            Ok(vec![Vector::unit_x() * 4, Vector::unit_y() * 4])
         );
     }
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "88");
+        assert_example::<Puzzle>(2022, 9, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "36");
+        assert_example::<Puzzle>(2022, 9, "example", 2);
     }
 }
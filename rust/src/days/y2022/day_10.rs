@@ -1,7 +1,9 @@
 use std::collections::BTreeMap;
 
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Instruction {
+pub enum Instruction {
     Noop,
     Addx(i32),
 }
@@ -35,14 +37,12 @@ impl Cpu {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Instruction> {
-    input
-        .lines()
-        .map(|l| match l.split_once(' ') {
-            Some((_ins, arg)) => Instruction::Addx(arg.parse().unwrap()),
-            None => Instruction::Noop,
-        })
-        .collect()
+fn parse_input(input: &str) -> Result<Vec<Instruction>, SolveError> {
+    parse_lines(input, |l| match l.split_once(' ') {
+        Some(("addx", arg)) => Ok(Instruction::Addx(arg.parse()?)),
+        None if l == "noop" => Ok(Instruction::Noop),
+        _ => Err(SolveError::new("unknown instruction")),
+    })
 }
 
 fn complete_history(history: BTreeMap<usize, i32>) -> Vec<i32> {
@@ -75,7 +75,7 @@ fn get_signal_strengths(start: usize, increment: usize, history: BTreeMap<usize,
         if key < start.saturating_sub(1) {
             return false;
         }
-        (key + 1 - start) % increment == 0
+        (key + 1 - start).is_multiple_of(increment)
     };
 
     let history = complete_history(history);
@@ -94,30 +94,11 @@ fn get_drawing(length: usize, history: BTreeMap<usize, i32>) -> Vec<Vec<bool>> {
         .enumerate()
         .map(|(i, v)| (*v - (i % length) as i32).abs() < 2)
         .collect::<Vec<_>>();
-    history
-        .chunks(length)
-        .map(|c| c.iter().cloned().collect())
-        .collect()
+    // The history ends with the state after the last cycle, which is not drawn
+    history.chunks_exact(length).map(|c| c.to_vec()).collect()
 }
 
-fn format_drawing(drawing: Vec<Vec<bool>>) -> String {
-    let mut output = String::new();
-    drawing.iter().for_each(|line| {
-        let mut out = String::new();
-        line.iter().for_each(|c| match c {
-            true => out.push('#'),
-            false => out.push(' '),
-        });
-        output.push('\n');
-        output.push_str(&out);
-    });
-
-    output
-}
-
-pub fn puzzle_1(input: &str) -> String {
-    let instructions = parse_input(input);
-
+fn puzzle_1(instructions: &[Instruction]) -> Result<Answer, SolveError> {
     let mut history = BTreeMap::new();
 
     let mut cpu = Cpu::new();
@@ -130,12 +111,10 @@ pub fn puzzle_1(input: &str) -> String {
 
     let strength: i32 = get_signal_strengths(20, 40, history).iter().sum();
 
-    strength.to_string()
+    Ok(strength.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let instructions = parse_input(input);
-
+fn puzzle_2(instructions: &[Instruction]) -> Result<Answer, SolveError> {
     let mut history = BTreeMap::new();
 
     let mut cpu = Cpu::new();
@@ -145,164 +124,37 @@ pub fn puzzle_2(input: &str) -> String {
         history.insert(cpu.counter, cpu.register_x);
     });
     let drawing = get_drawing(40, history);
-    format_drawing(drawing)
+    Ok(Answer::Grid(drawing))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "addx 15
-addx -11
-addx 6
-addx -3
-addx 5
-addx -1
-addx -8
-addx 13
-addx 4
-noop
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx -35
-addx 1
-addx 24
-addx -19
-addx 1
-addx 16
-addx -11
-noop
-noop
-addx 21
-addx -15
-noop
-noop
-addx -3
-addx 9
-addx 1
-addx -3
-addx 8
-addx 1
-addx 5
-noop
-noop
-noop
-noop
-noop
-addx -36
-noop
-addx 1
-addx 7
-noop
-noop
-noop
-addx 2
-addx 6
-noop
-noop
-noop
-noop
-noop
-addx 1
-noop
-noop
-addx 7
-addx 1
-noop
-addx -13
-addx 13
-addx 7
-noop
-addx 1
-addx -33
-noop
-noop
-noop
-addx 2
-noop
-noop
-noop
-addx 8
-noop
-addx -1
-addx 2
-addx 1
-noop
-addx 17
-addx -9
-addx 1
-addx 1
-addx -3
-addx 11
-noop
-noop
-addx 1
-noop
-addx 1
-noop
-noop
-addx -13
-addx -19
-addx 1
-addx 3
-addx 26
-addx -30
-addx 12
-addx -1
-addx 3
-addx 1
-noop
-noop
-noop
-addx -9
-addx 18
-addx 1
-addx 2
-noop
-noop
-addx 9
-noop
-noop
-noop
-addx -1
-addx 2
-addx -37
-addx 1
-addx 3
-noop
-addx 15
-addx -21
-addx 22
-addx -6
-addx 1
-noop
-addx 2
-addx 1
-noop
-addx -10
-noop
-noop
-addx 20
-addx 1
-addx 2
-addx 2
-addx -6
-addx -11
-noop
-noop
-noop";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_parse_instruction() {
         let instructions = "noop\naddx 3\naddx -5";
-        let instructions = parse_input(instructions);
+        let instructions = parse_input(instructions).unwrap();
         assert_eq!(
             instructions,
             vec![
@@ -337,6 +189,11 @@ noop";
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "13140");
+        assert_example::<Puzzle>(2022, 10, "example", 1);
+    }
+
+    #[test]
+    fn test_puzzle_2() {
+        assert_example::<Puzzle>(2022, 10, "example", 2);
     }
 }
@@ -1,10 +1,13 @@
+use crate::utils::{Answer, PuzzleContext, Solution, SolveError};
+
 mod types {
-    use std::collections::VecDeque;
+    use std::{collections::VecDeque, rc::Rc};
 
     pub type Operation = dyn Fn(u64) -> Option<u64>;
+    #[derive(Clone)]
     pub struct Monkey {
         pub items: VecDeque<u64>,
-        pub operation: Box<Operation>,
+        pub operation: Rc<Operation>,
         pub test_number: u64,
         pub recipient_success: usize,
         pub recipient_failed: usize,
@@ -32,10 +35,10 @@ mod types {
 }
 
 mod parser {
-    use core::panic;
-    use std::num::ParseIntError;
+    use std::{num::ParseIntError, rc::Rc};
 
     pub use super::types::Monkey;
+    use crate::utils::{parse_blocks, SolveError};
 
     #[derive(Debug, Clone, Copy)]
     enum Operand {
@@ -53,66 +56,89 @@ mod parser {
         }
     }
 
-    fn parse_operation(input: &str) -> Box<super::types::Operation> {
-        let input = input.strip_prefix(" new = ").unwrap();
+    fn parse_operation(input: &str) -> Result<Rc<super::types::Operation>, SolveError> {
+        let input = input
+            .strip_prefix(" new = ")
+            .ok_or("expected an operation")?;
         let mut input = input.split(' ').map(|x| x.trim());
 
-        let operand_1_str = input.next().unwrap();
-        let operator_str = input.next().unwrap();
-        let operand_2_str = input.next().unwrap();
+        let operand_1_str = input.next().ok_or("missing operand")?;
+        let operator_str = input.next().ok_or("missing operator")?;
+        let operand_2_str = input.next().ok_or("missing operand")?;
 
-        let operand_1 = map_operand(operand_1_str).unwrap();
-        let operand_2 = map_operand(operand_2_str).unwrap();
+        let operand_1 = map_operand(operand_1_str)?;
+        let operand_2 = map_operand(operand_2_str)?;
 
-        let operator = match operator_str.chars().next().unwrap() {
-            '+' => u64::checked_add,
-            '*' => u64::checked_mul,
-            _ => panic!(),
+        let operator = match operator_str {
+            "+" => u64::checked_add,
+            "*" => u64::checked_mul,
+            _ => return Err(SolveError::new("unknown operator")),
         };
 
-        let operation: Box<dyn Fn(u64) -> Option<u64>> = match (operand_1, operand_2) {
-            (Operand::Old, Operand::Old) => Box::new(move |old| operator(old, old)),
-            (Operand::Old, Operand::Number(n2)) => Box::new(move |old| operator(old, n2)),
-            (Operand::Number(n1), Operand::Old) => Box::new(move |old| operator(n1, old)),
-            (Operand::Number(n1), Operand::Number(n2)) => Box::new(move |_| operator(n1, n2)),
+        let operation: Rc<dyn Fn(u64) -> Option<u64>> = match (operand_1, operand_2) {
+            (Operand::Old, Operand::Old) => Rc::new(move |old| operator(old, old)),
+            (Operand::Old, Operand::Number(n2)) => Rc::new(move |old| operator(old, n2)),
+            (Operand::Number(n1), Operand::Old) => Rc::new(move |old| operator(n1, old)),
+            (Operand::Number(n1), Operand::Number(n2)) => Rc::new(move |_| operator(n1, n2)),
         };
 
-        operation
+        Ok(operation)
     }
 
-    fn get_last_number(input: &str) -> usize {
-        input.rsplit_once(' ').unwrap().1.parse().unwrap()
+    fn get_last_number(input: &str) -> Result<usize, SolveError> {
+        let (_, number) = input.rsplit_once(' ').ok_or("expected a number")?;
+        Ok(number.parse()?)
     }
 
-    fn parse_monkey(input: &str) -> Monkey {
-        let mut lines = input.lines();
-        lines.next(); // monkey line
-        let items = lines
-            .next()
-            .unwrap()
-            .split_once(": ")
-            .unwrap()
-            .1
+    fn parse_items(input: &str) -> Result<Vec<u64>, SolveError> {
+        let (_, items) = input.split_once(": ").ok_or("expected starting items")?;
+        Ok(items
             .split(", ")
-            .map(|i| i.parse::<u64>().unwrap())
-            .collect::<Vec<_>>();
-        let operation =
-            parse_operation(lines.next().unwrap().strip_prefix("  Operation:").unwrap());
-        let test_number = get_last_number(lines.next().unwrap()) as u64;
-        let recipient_success = get_last_number(lines.next().unwrap());
-        let recipient_failed = get_last_number(lines.next().unwrap());
-
-        Monkey {
+            .map(|i| i.parse::<u64>())
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn parse_monkey(input: &str) -> Result<Monkey, SolveError> {
+        let lines = input.lines().collect::<Vec<_>>();
+        if lines.len() < 6 {
+            return Err(SolveError::new("incomplete monkey description"));
+        }
+        // Errors are reported on the line of the monkey description they come from
+        let at_line = |i: usize, e: SolveError| e.at_line(i + 1, lines[i]);
+
+        let items = parse_items(lines[1]).map_err(|e| at_line(1, e))?;
+        let operation = lines[2]
+            .strip_prefix("  Operation:")
+            .ok_or_else(|| SolveError::new("expected an operation"))
+            .and_then(parse_operation)
+            .map_err(|e| at_line(2, e))?;
+        let test_number = get_last_number(lines[3]).map_err(|e| at_line(3, e))? as u64;
+        let recipient_success = get_last_number(lines[4]).map_err(|e| at_line(4, e))?;
+        let recipient_failed = get_last_number(lines[5]).map_err(|e| at_line(5, e))?;
+
+        if test_number == 0 {
+            let error = SolveError::new("cannot test divisibility by 0");
+            return Err(at_line(3, error));
+        }
+
+        Ok(Monkey {
             items: items.into(),
             operation,
             test_number,
             recipient_success,
             recipient_failed,
-        }
+        })
     }
 
-    pub fn parse_input(input: &str) -> Vec<Monkey> {
-        input.split("\n\n").map(|l| parse_monkey(l)).collect()
+    pub fn parse_input(input: &str) -> Result<Vec<Monkey>, SolveError> {
+        let monkeys = parse_blocks(input, parse_monkey)?;
+        let unknown_recipient = monkeys
+            .iter()
+            .any(|m| m.recipient_success >= monkeys.len() || m.recipient_failed >= monkeys.len());
+        match unknown_recipient {
+            true => Err(SolveError::new("a monkey throws to an unknown monkey")),
+            false => Ok(monkeys),
+        }
     }
 
     #[test]
@@ -124,7 +150,7 @@ mod parser {
     If true: throw to monkey 2
     If false: throw to monkey 0";
 
-        let monkey = parse_monkey(input);
+        let monkey = parse_monkey(input).unwrap();
 
         assert_eq!(monkey.items, vec![54, 65, 75, 74]);
         assert_eq!(monkey.test_number, 19);
@@ -174,62 +200,54 @@ fn monkey_play(
     pass
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let monkeys = parser::parse_input(input);
-    let mut pass = monkey_play(20, monkeys, true);
+fn monkey_business(mut pass: Vec<usize>) -> Result<Answer, SolveError> {
     pass.sort();
 
-    (pass.pop().unwrap() * pass.pop().unwrap()).to_string()
+    match (pass.pop(), pass.pop()) {
+        (Some(first), Some(second)) => Ok((first * second).into()),
+        _ => Err(SolveError::new("expected at least two monkeys")),
+    }
+}
+
+fn puzzle_1(monkeys: &[types::Monkey]) -> Result<Answer, SolveError> {
+    monkey_business(monkey_play(20, monkeys.to_vec(), true))
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let monkeys = parser::parse_input(input);
-    let mut pass = monkey_play(10000, monkeys, false);
+fn puzzle_2(monkeys: &[types::Monkey]) -> Result<Answer, SolveError> {
+    monkey_business(monkey_play(10000, monkeys.to_vec(), false))
+}
 
-    pass.sort();
+pub struct Puzzle;
 
-    (pass.pop().unwrap() * pass.pop().unwrap()).to_string()
+impl Solution for Puzzle {
+    type Parsed = Vec<types::Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parser::parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "Monkey 0:
-  Starting items: 79, 98
-  Operation: new = old * 19
-  Test: divisible by 23
-    If true: throw to monkey 2
-    If false: throw to monkey 3
-
-Monkey 1:
-  Starting items: 54, 65, 75, 74
-  Operation: new = old + 6
-  Test: divisible by 19
-    If true: throw to monkey 2
-    If false: throw to monkey 0
-
-Monkey 2:
-  Starting items: 79, 60, 97
-  Operation: new = old * old
-  Test: divisible by 13
-    If true: throw to monkey 1
-    If false: throw to monkey 3
-
-Monkey 3:
-  Starting items: 74
-  Operation: new = old + 3
-  Test: divisible by 17
-    If true: throw to monkey 0
-    If false: throw to monkey 1";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "10605");
+        assert_example::<Puzzle>(2022, 11, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "2713310158");
+        assert_example::<Puzzle>(2022, 11, "example", 2);
     }
 }
@@ -3,7 +3,7 @@ use std::{
     collections::{BTreeMap, BTreeSet},
 };
 
-use crate::utils::geometry::Point2;
+use crate::utils::{geometry::Point2, parse_lines, Answer, PuzzleContext, Solution, SolveError};
 
 type Point = Point2<u16>;
 
@@ -43,18 +43,16 @@ impl Point {
 
 impl Ord for TreeNode {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.path
+            .len()
+            .cmp(&other.path.len())
+            .then(self.position.cmp(&other.position))
     }
 }
 
 impl PartialOrd for TreeNode {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(
-            self.path
-                .len()
-                .cmp(&other.path.len())
-                .then(self.position.cmp(&other.position)),
-        )
+        Some(self.cmp(other))
     }
 }
 
@@ -64,31 +62,46 @@ impl PartialEq for TreeNode {
     }
 }
 
-fn parse_input(input: &str) -> (Point, Point, BTreeMap<Point, char>) {
-    let mut height_map_char: BTreeMap<Point, char> = input
-        .split('\n')
-        .filter(|line| !line.is_empty())
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().map({
-                move |(x, h)| {
-                    let coords = Point {
-                        x: x as u16,
-                        y: y as u16,
-                    };
-                    (coords, h)
-                }
-            })
+fn parse_input(input: &str) -> Result<(Point, Point, BTreeMap<Point, char>), SolveError> {
+    let mut height_map_char: BTreeMap<Point, char> = parse_lines(input, |line| {
+        match line
+            .chars()
+            .all(|h| h.is_ascii_lowercase() || h == 'S' || h == 'E')
+        {
+            true => Ok(line.to_string()),
+            false => Err(SolveError::new("unexpected height")),
+        }
+    })?
+    .iter()
+    .enumerate()
+    .flat_map(|(y, line)| {
+        line.chars().enumerate().map({
+            move |(x, h)| {
+                let coords = Point {
+                    x: x as u16,
+                    y: y as u16,
+                };
+                (coords, h)
+            }
         })
-        .collect();
-
-    let &start = height_map_char.iter().find(|&(_, h)| *h == 'S').unwrap().0;
-    let &goal = height_map_char.iter().find(|&(_, h)| *h == 'E').unwrap().0;
+    })
+    .collect();
+
+    let &start = height_map_char
+        .iter()
+        .find(|&(_, h)| *h == 'S')
+        .ok_or("missing start position")?
+        .0;
+    let &goal = height_map_char
+        .iter()
+        .find(|&(_, h)| *h == 'E')
+        .ok_or("missing goal position")?
+        .0;
 
     *height_map_char.get_mut(&start).unwrap() = 'a';
     *height_map_char.get_mut(&goal).unwrap() = 'z';
 
-    (start, goal, height_map_char)
+    Ok((start, goal, height_map_char))
 }
 
 fn find_accesible_squares(
@@ -117,7 +130,7 @@ fn find_accesible_squares(
 }
 
 fn find_solution(
-    height_map: BTreeMap<Point, char>,
+    height_map: &BTreeMap<Point, char>,
     start: Option<Point>,
     end: Point,
 ) -> Vec<Point> {
@@ -151,7 +164,7 @@ fn find_solution(
     let mut results = vec![];
 
     while let Some(node) = queue.pop_first() {
-        let accessible_positions = find_accesible_squares(&height_map, node.position);
+        let accessible_positions = find_accesible_squares(height_map, node.position);
 
         for position in accessible_positions {
             if position == node.position {
@@ -195,27 +208,49 @@ fn find_solution(
     vec![]
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let (start, goal, height_map) = parse_input(input);
-    let solution = find_solution(height_map, Some(start), goal);
+fn puzzle_1(
+    (start, goal, height_map): &(Point, Point, BTreeMap<Point, char>),
+) -> Result<Answer, SolveError> {
+    let solution = find_solution(height_map, Some(*start), *goal);
+    if solution.is_empty() {
+        return Err(SolveError::new("the goal cannot be reached"));
+    }
 
-    solution.len().to_string()
+    Ok(solution.len().into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let (_, goal, height_map) = parse_input(input);
-    let solution = find_solution(height_map, None, goal);
+fn puzzle_2(
+    (_, goal, height_map): &(Point, Point, BTreeMap<Point, char>),
+) -> Result<Answer, SolveError> {
+    let solution = find_solution(height_map, None, *goal);
+    if solution.is_empty() {
+        return Err(SolveError::new("the goal cannot be reached"));
+    }
 
-    solution.len().to_string()
+    Ok(solution.len().into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = (Point, Point, BTreeMap<Point, char>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "Sabqponm
-abcryxxl
-accszExk
-acctuvwj
-abdefghi";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
@@ -223,7 +258,7 @@ abdefghi";
     fn test_can_parse_input() {
         let input = "Sab\nabc\nacE\n";
 
-        let (start, goal, height_map) = parse_input(input);
+        let (start, goal, height_map) = parse_input(input).unwrap();
         assert_eq!(start, Point { x: 0, y: 0 });
         assert_eq!(goal, Point { x: 2, y: 2 });
 
@@ -235,7 +270,7 @@ abdefghi";
     fn test_find_accessible_squares() {
         let input = "Sab\nabc\nacE\n";
 
-        let (_, _, height_map) = parse_input(input);
+        let (_, _, height_map) = parse_input(input).unwrap();
 
         let accessible_positions = find_accesible_squares(&height_map, Point { x: 2, y: 1 });
         assert_eq!(
@@ -246,15 +281,11 @@ abdefghi";
 
     #[test]
     fn test_puzzle_1() {
-        let result = puzzle_1(INPUT);
-
-        assert_eq!(result, "31");
+        assert_example::<Puzzle>(2022, 12, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        let result = puzzle_2(INPUT);
-
-        assert_eq!(result, "29");
+        assert_example::<Puzzle>(2022, 12, "example", 2);
     }
 }
@@ -1,96 +1,95 @@
 use std::collections::BTreeSet;
 
+use crate::utils::{parse_blocks, Answer, PuzzleContext, Solution, SolveError};
+
 #[derive(Debug, PartialEq, Eq, Clone)]
-enum ListElement {
+pub enum ListElement {
     Number(u8),
     List(Vec<ListElement>),
 }
 
 impl Ord for ListElement {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
-impl PartialOrd for ListElement {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         match (self, other) {
-            (ListElement::Number(a), ListElement::Number(b)) => a.partial_cmp(b),
-            (ListElement::List(list_a), ListElement::List(list_b)) => list_a.partial_cmp(list_b),
+            (ListElement::Number(a), ListElement::Number(b)) => a.cmp(b),
+            (ListElement::List(list_a), ListElement::List(list_b)) => list_a.cmp(list_b),
             (ListElement::Number(a), ListElement::List(list_b)) => {
-                vec![ListElement::Number(*a)].partial_cmp(list_b)
+                vec![ListElement::Number(*a)].cmp(list_b)
             }
             (ListElement::List(list_a), ListElement::Number(b)) => {
-                list_a.partial_cmp(&vec![ListElement::Number(*b)])
+                list_a.cmp(&vec![ListElement::Number(*b)])
             }
         }
     }
 }
 
-fn parse_input_list(input: &str) -> Option<ListElement> {
+impl PartialOrd for ListElement {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn parse_input_list(input: &str) -> Result<Option<ListElement>, SolveError> {
     if input.is_empty() {
-        return None;
+        return Ok(None);
     }
 
     if !(input.starts_with('[') && input.ends_with(']')) {
-        return Some(ListElement::Number(input.parse().unwrap()));
+        return Ok(Some(ListElement::Number(input.parse()?)));
     }
 
     let slice = &input[1..input.len() - 1];
 
     let mut res = vec![];
-    let mut it = slice.chars();
     let mut buf = String::new();
     let mut index = 0;
-    loop {
-        match it.next() {
-            Some(c) => match c {
-                ',' => {
-                    if index == 0 {
-                        if let Some(parsed) = parse_input_list(&buf) {
-                            res.push(parsed);
-                        }
-                        buf.clear();
-                    } else {
-                        buf.push(c);
+    for c in slice.chars() {
+        match c {
+            ',' => {
+                if index == 0 {
+                    if let Some(parsed) = parse_input_list(&buf)? {
+                        res.push(parsed);
                     }
-                }
-                '[' => {
-                    index += 1;
+                    buf.clear();
+                } else {
                     buf.push(c);
                 }
-                ']' => {
-                    index -= 1;
-                    buf.push(c);
-                }
-                _ => buf.push(c),
-            },
-            None => break,
+            }
+            '[' => {
+                index += 1;
+                buf.push(c);
+            }
+            ']' => {
+                index -= 1;
+                buf.push(c);
+            }
+            _ => buf.push(c),
         }
     }
 
-    if let Some(parsed) = parse_input_list(&buf) {
+    if index != 0 {
+        return Err(SolveError::new("unbalanced brackets"));
+    }
+    if let Some(parsed) = parse_input_list(&buf)? {
         res.push(parsed);
     }
-    Some(ListElement::List(res))
+    Ok(Some(ListElement::List(res)))
 }
 
-fn parse_input(input: &str) -> Vec<(ListElement, ListElement)> {
-    input
-        .split("\n\n")
-        .map(|l| {
-            let (a, b) = l.split_once('\n').unwrap();
-            (
-                parse_input_list(a.trim()).unwrap(),
-                parse_input_list(b.trim()).unwrap(),
-            )
-        })
-        .collect()
+fn parse_packet(input: &str) -> Result<ListElement, SolveError> {
+    parse_input_list(input.trim())?.ok_or_else(|| SolveError::new("expected a packet"))
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let pairs = parse_input(input);
+fn parse_input(input: &str) -> Result<Vec<(ListElement, ListElement)>, SolveError> {
+    parse_blocks(input, |l| {
+        let (a, b) = l.split_once('\n').ok_or("expected a pair of packets")?;
+        let a = parse_packet(a).map_err(|e| e.at_line(1, a))?;
+        let b = parse_packet(b).map_err(|e| e.at_line(2, b))?;
+        Ok((a, b))
+    })
+}
 
+fn puzzle_1(pairs: &[(ListElement, ListElement)]) -> Result<Answer, SolveError> {
     let sum: usize = pairs
         .iter()
         .map(|(left, right)| left <= right)
@@ -101,17 +100,16 @@ pub fn puzzle_1(input: &str) -> String {
         })
         .sum();
 
-    sum.to_string()
+    Ok(sum.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let input = parse_input(input);
+fn puzzle_2(input: &[(ListElement, ListElement)]) -> Result<Answer, SolveError> {
     let mut packets = input
-        .into_iter()
-        .flat_map(|(a, b)| vec![a, b])
+        .iter()
+        .flat_map(|(a, b)| vec![a.clone(), b.clone()])
         .collect::<BTreeSet<_>>();
-    let divider_1 = parse_input_list("[[2]]").unwrap();
-    let divider_2 = parse_input_list("[[6]]").unwrap();
+    let divider_1 = parse_packet("[[2]]")?;
+    let divider_2 = parse_packet("[[6]]")?;
 
     packets.insert(divider_1.clone());
     packets.insert(divider_2.clone());
@@ -119,34 +117,30 @@ pub fn puzzle_2(input: &str) -> String {
     let index_1 = packets.iter().position(|el| *el == divider_1).unwrap() + 1;
     let index_2 = packets.iter().position(|el| *el == divider_2).unwrap() + 1;
 
-    (index_1 * index_2).to_string()
+    Ok((index_1 * index_2).into())
 }
 
-#[cfg(test)]
-mod tests {
-    const INPUT: &str = "[1,1,3,1,1]
-[1,1,5,1,1]
-
-[[1],[2,3,4]]
-[[1],4]
+pub struct Puzzle;
 
-[9]
-[[8,7,6]]
+impl Solution for Puzzle {
+    type Parsed = Vec<(ListElement, ListElement)>;
 
-[[4,4],4,4]
-[[4,4],4,4,4]
-
-[7,7,7,7]
-[7,7,7]
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
 
-[]
-[3]
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
 
-[[[]]]
-[[]]
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
+}
 
-[1,[2,[3,[4,[5,6,7]]]],8,9]
-[1,[2,[3,[4,[5,6,0]]]],8,9]";
+#[cfg(test)]
+mod tests {
+    use crate::utils::fixtures::{assert_example, example_input};
 
     use super::*;
 
@@ -158,21 +152,21 @@ mod tests {
         let result = parse_input_list(input);
         assert_eq!(
             result,
-            Some(List(vec![
+            Ok(Some(List(vec![
                 List(vec![Number(4), Number(4)]),
                 Number(4),
                 Number(4)
-            ]))
+            ])))
         );
 
         let input = "[]";
         let result = parse_input_list(input);
-        assert_eq!(result, Some(List(vec![])));
+        assert_eq!(result, Ok(Some(List(vec![]))));
     }
 
     #[test]
     fn test_can_parse_input() {
-        let result = parse_input(INPUT);
+        let result = parse_input(example_input(2022, 13, "example")).unwrap();
 
         assert_eq!(result.len(), 8);
     }
@@ -180,43 +174,43 @@ mod tests {
     #[test]
     fn test_orderings() {
         let input = "[1,1,3,1,1]\n[1,1,5,1,1]";
-        let (a, b) = parse_input(input).first().unwrap().clone();
+        let (a, b) = parse_input(input).unwrap().first().unwrap().clone();
 
         assert!(a < b);
 
         let input = "[[1],[2,3,4]]\n[[1],4]";
-        let (a, b) = parse_input(input).first().unwrap().clone();
+        let (a, b) = parse_input(input).unwrap().first().unwrap().clone();
 
         assert!(a < b);
 
         let input = "[9]\n[[8,7,6]]";
-        let (a, b) = parse_input(input).first().unwrap().clone();
+        let (a, b) = parse_input(input).unwrap().first().unwrap().clone();
 
         assert!(a > b);
 
         let input = "[[4,4],4,4]\n[[4,4],4,4,4]";
-        let (a, b) = parse_input(input).first().unwrap().clone();
+        let (a, b) = parse_input(input).unwrap().first().unwrap().clone();
 
         assert!(a < b);
 
         let input = "[7,7,7,7]\n[7,7,7]";
-        let (a, b) = parse_input(input).first().unwrap().clone();
+        let (a, b) = parse_input(input).unwrap().first().unwrap().clone();
 
         assert!(a > b);
 
         let input = "[1,[2,[3,[4,[5,6,7]]]],8,9]\n[1,[2,[3,[4,[5,6,0]]]],8,9]";
-        let (a, b) = parse_input(input).first().unwrap().clone();
+        let (a, b) = parse_input(input).unwrap().first().unwrap().clone();
 
         assert!(a > b);
     }
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "13");
+        assert_example::<Puzzle>(2022, 13, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "140");
+        assert_example::<Puzzle>(2022, 13, "example", 2);
     }
 }
@@ -1,11 +1,11 @@
 use std::ops::RangeInclusive;
 
-use crate::utils::geometry::Point2;
+use crate::utils::{geometry::Point2, parse_lines, Answer, PuzzleContext, Solution, SolveError};
 
 type Point = Point2<usize>;
 
-#[derive(Debug)]
-struct Map {
+#[derive(Debug, Clone)]
+pub struct Map {
     bottom_height: usize,
     offset_x: usize,
     data: Vec<Vec<bool>>,
@@ -29,23 +29,37 @@ fn get_range(start: usize, finish: usize) -> RangeInclusive<usize> {
     }
 }
 
-fn parse_input(input: &str) -> Map {
-    let result = input.lines().map(|l| {
+fn parse_input(input: &str) -> Result<Map, SolveError> {
+    let result = parse_lines(input, |l| {
         l.split(" -> ")
             .map(|c| {
-                let (a, b) = c.split_once(',').unwrap();
-                (a.parse::<usize>().unwrap(), b.parse::<usize>().unwrap())
+                let (a, b) = c.split_once(',').ok_or("expected a point as x,y")?;
+                Ok((a.parse::<usize>()?, b.parse::<usize>()?))
             })
-            .collect::<Vec<_>>()
-    });
+            .collect::<Result<Vec<_>, SolveError>>()
+    })?;
 
-    let max_y = result.clone().flatten().max_by_key(|c| c.1).unwrap().1 + 1;
+    let max_y = result
+        .iter()
+        .flatten()
+        .max_by_key(|c| c.1)
+        .ok_or("expected at least one rock path")?
+        .1
+        + 1;
 
     let bottom_height = max_y + 1;
-    let offset_x = 500 - bottom_height;
+    let offset_x = 500usize
+        .checked_sub(bottom_height)
+        .ok_or("the rocks are too deep")?;
+    if result
+        .iter()
+        .flatten()
+        .any(|c| c.0 < offset_x || c.0 > offset_x + 2 * bottom_height)
+    {
+        return Err(SolveError::new("a rock is too far from the sand source"));
+    }
 
-    let mut data: Vec<Vec<bool>> = vec![];
-    data.reserve(bottom_height + 1);
+    let mut data: Vec<Vec<bool>> = Vec::with_capacity(bottom_height + 1);
 
     for y in 0..=bottom_height {
         data.insert(y, vec![false; 2 * bottom_height + 1]);
@@ -75,7 +89,7 @@ fn parse_input(input: &str) -> Map {
         });
     }
 
-    output_map
+    Ok(output_map)
 }
 
 fn sand_fall(map: &Map, point: Point) -> Point {
@@ -111,46 +125,63 @@ fn pour_sand(map: &mut Map, has_floor: bool) -> bool {
     }
 }
 
-pub fn puzzle_1(input: &str) -> String {
+fn puzzle_1(map: &Map) -> Result<Answer, SolveError> {
     let mut count = 0;
-    let mut map = parse_input(input);
+    let mut map = map.clone();
 
     while !pour_sand(&mut map, false) {
         count += 1;
     }
-    count.to_string()
+    Ok(count.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
+fn puzzle_2(map: &Map) -> Result<Answer, SolveError> {
     let mut count = 0;
-    let mut map = parse_input(input);
+    let mut map = map.clone();
 
     while !pour_sand(&mut map, true) {
         count += 1;
     }
-    (count + 1).to_string()
+    Ok((count + 1).into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "498,4 -> 498,6 -> 496,6
-503,4 -> 502,4 -> 502,9 -> 494,9";
+    use crate::utils::fixtures::{assert_example, example_input};
 
     use super::*;
 
     #[test]
     fn test_can_parse_input() {
-        let parsed = parse_input(INPUT);
+        let parsed = parse_input(example_input(2022, 14, "example")).unwrap();
         assert_eq!(parsed.data.iter().flatten().filter(|x| **x).count(), 20);
     }
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "24");
+        assert_example::<Puzzle>(2022, 14, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "93");
+        assert_example::<Puzzle>(2022, 14, "example", 2);
     }
 }
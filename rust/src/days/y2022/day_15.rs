@@ -1,10 +1,13 @@
 use std::{collections::BTreeMap, ops::RangeInclusive};
 
-use crate::utils::geometry::{point2, Point2};
+use crate::utils::{
+    geometry::{point2, Point2},
+    parse_lines, Answer, PuzzleContext, Solution, SolveError,
+};
 
 type Point = Point2<i32>;
 
-pub(crate) struct Sensor {
+pub struct Sensor {
     closest_beacon: Point,
     distance: i32,
 }
@@ -17,33 +20,47 @@ enum MapState {
     Sensor,
 }
 
-fn parse_coord(input: &str) -> Point {
-    let (x, y) = input.split_once(',').unwrap();
-    point2!(
-        x.trim().strip_prefix("x=").unwrap().parse().unwrap(),
-        y.trim().strip_prefix("y=").unwrap().parse().unwrap()
-    )
+fn parse_coord(input: &str) -> Result<Point, SolveError> {
+    let (x, y) = input
+        .split_once(',')
+        .ok_or("expected a position as x=.., y=..")?;
+    let x = x
+        .trim()
+        .strip_prefix("x=")
+        .ok_or("expected an x coordinate")?;
+    let y = y
+        .trim()
+        .strip_prefix("y=")
+        .ok_or("expected a y coordinate")?;
+    Ok(point2!(x.parse()?, y.parse()?))
 }
 
-fn parse_input(input: &str) -> BTreeMap<Point, Sensor> {
-    input
-        .lines()
-        .map(|l| {
-            let (sensor_line, beacon_line) = l.split_once(':').unwrap();
-            let sensor = parse_coord(sensor_line.strip_prefix("Sensor at ").unwrap());
-            let closest_beacon =
-                parse_coord(beacon_line.strip_prefix(" closest beacon is at ").unwrap());
-            let distance = sensor.distance_1(&closest_beacon);
-
-            (
-                sensor,
-                Sensor {
-                    closest_beacon,
-                    distance,
-                },
-            )
-        })
-        .collect()
+fn parse_input(input: &str) -> Result<BTreeMap<Point, Sensor>, SolveError> {
+    let sensors = parse_lines(input, |l| {
+        let (sensor_line, beacon_line) =
+            l.split_once(':').ok_or("expected a sensor and a beacon")?;
+        let sensor = parse_coord(
+            sensor_line
+                .strip_prefix("Sensor at ")
+                .ok_or("expected a sensor")?,
+        )?;
+        let closest_beacon = parse_coord(
+            beacon_line
+                .strip_prefix(" closest beacon is at ")
+                .ok_or("expected a beacon")?,
+        )?;
+        let distance = sensor.distance_1(&closest_beacon);
+
+        Ok((
+            sensor,
+            Sensor {
+                closest_beacon,
+                distance,
+            },
+        ))
+    })?;
+
+    Ok(sensors.into_iter().collect())
 }
 
 fn get_footprint(map: &BTreeMap<Point, Sensor>) -> (RangeInclusive<i32>, RangeInclusive<i32>) {
@@ -144,70 +161,58 @@ fn get_first_empty_position(subgrid_size: i32, map: &BTreeMap<Point, Sensor>) ->
     None
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let row_number = 2_000_000;
-
-    let map = parse_input(input);
-    let footprint = get_footprint(&map);
-    get_row(row_number, &map, footprint)
+fn puzzle_1(map: &BTreeMap<Point, Sensor>, row_number: i32) -> Result<Answer, SolveError> {
+    let footprint = get_footprint(map);
+    Ok(get_row(row_number, map, footprint)
         .iter()
         .filter(|&&state| state == MapState::Empty)
         .count()
-        .to_string()
+        .into())
+}
+
+fn puzzle_2(map: &BTreeMap<Point, Sensor>, max_coord: i32) -> Result<Answer, SolveError> {
+    let first_empty_position =
+        get_first_empty_position(max_coord, map).ok_or("no position for the distress beacon")?;
+
+    // The tuning frequency does not depend on the size of the searched area
+    let value = first_empty_position.x as i64 * 4_000_000 + first_empty_position.y as i64;
+    Ok(value.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let max_coord = 4_000_000;
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = BTreeMap<Point, Sensor>;
+
+    const PARAMS: &'static [(&'static str, &'static str)] =
+        &[("row", "2000000"), ("max_coord", "4000000")];
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
 
-    let map = parse_input(input);
-    let first_empty_position = get_first_empty_position(max_coord, &map).unwrap();
+    fn part_1(input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input, context.param("row")?)
+    }
 
-    let value = first_empty_position.x as i64 * max_coord as i64 + first_empty_position.y as i64;
-    value.to_string()
+    fn part_2(input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input, context.param("max_coord")?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
-Sensor at x=9, y=16: closest beacon is at x=10, y=16
-Sensor at x=13, y=2: closest beacon is at x=15, y=3
-Sensor at x=12, y=14: closest beacon is at x=10, y=16
-Sensor at x=10, y=20: closest beacon is at x=10, y=16
-Sensor at x=14, y=17: closest beacon is at x=10, y=16
-Sensor at x=8, y=7: closest beacon is at x=2, y=10
-Sensor at x=2, y=0: closest beacon is at x=2, y=10
-Sensor at x=0, y=11: closest beacon is at x=2, y=10
-Sensor at x=20, y=14: closest beacon is at x=25, y=17
-Sensor at x=17, y=20: closest beacon is at x=21, y=22
-Sensor at x=16, y=7: closest beacon is at x=15, y=3
-Sensor at x=14, y=3: closest beacon is at x=15, y=3
-Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_puzzle_1() {
-        let row_number = 10;
-
-        let map = parse_input(INPUT);
-        let footprint = get_footprint(&map);
-        let result = get_row(row_number, &map, footprint)
-            .iter()
-            .filter(|&&state| state == MapState::Empty)
-            .count();
-
-        assert_eq!(result, 26);
+        assert_example::<Puzzle>(2022, 15, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        let max_coord = 20;
-
-        let map = parse_input(INPUT);
-        let first_empty_position = get_first_empty_position(max_coord, &map).unwrap();
-
-        let value = first_empty_position.x as i64 * 4_000_000i64 + first_empty_position.y as i64;
-
-        assert_eq!(value, 56000011);
+        assert_example::<Puzzle>(2022, 15, "example", 2);
     }
 }
@@ -3,9 +3,11 @@ use std::{
     hash::{Hash, Hasher},
 };
 
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError, Variant};
+
 type ValveName = (char, char);
 
-pub(crate) struct InputData {
+pub struct InputData {
     paths: HashMap<ValveName, (Vec<ValveName>, u16)>,
     flow_rate: Vec<(ValveName, u16)>,
 }
@@ -85,48 +87,63 @@ impl Hash for Node {
     }
 }
 
-fn parse_valve_name(input: &str) -> ValveName {
+fn parse_valve_name(input: &str) -> Result<ValveName, SolveError> {
     let mut input = input.chars();
-    (input.next().unwrap(), input.next().unwrap())
+    match (input.next(), input.next()) {
+        (Some(a), Some(b)) if a.is_ascii_uppercase() && b.is_ascii_uppercase() => Ok((a, b)),
+        _ => Err(SolveError::new(
+            "expected a valve name of two capital letters",
+        )),
+    }
 }
 
-fn parse_input(input: &str) -> InputData {
+fn parse_input(input: &str) -> Result<InputData, SolveError> {
     // A line will look like that:
     //   Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
-    let parsed = input.lines().map(|line| {
-        let (valve_line, tunnel_line) = line.split_once("; ").unwrap();
+    let parsed = parse_lines(input, |line| {
+        let (valve_line, tunnel_line) =
+            line.split_once("; ").ok_or("expected valve and tunnels")?;
         let (valve_str, valve_line) = valve_line
             .strip_prefix("Valve ")
-            .unwrap()
-            .split_once(' ')
-            .unwrap();
-        let valve = parse_valve_name(valve_str);
-        let flow_rate = valve_line.split_once('=').unwrap().1.parse().unwrap();
+            .and_then(|v| v.split_once(' '))
+            .ok_or("expected a valve")?;
+        let valve = parse_valve_name(valve_str)?;
+        let (_, flow_rate) = valve_line.split_once('=').ok_or("expected a flow rate")?;
+        let flow_rate: u16 = flow_rate.parse()?;
 
         let tunnels = tunnel_line
             .split_once("to ")
-            .unwrap()
-            .1
-            .split_once(' ')
-            .unwrap()
+            .and_then(|(_, t)| t.split_once(' '))
+            .ok_or("expected tunnels")?
             .1
             .split(' ')
-            .map(|v| parse_valve_name(v))
-            .collect();
+            .map(parse_valve_name)
+            .collect::<Result<Vec<_>, _>>()?;
 
-        (valve, (flow_rate, tunnels))
-    });
+        Ok((valve, (flow_rate, tunnels)))
+    })?;
 
     let flow_rate = parsed
-        .clone()
-        .map(|(name, (flow_rate, _))| (name, flow_rate))
+        .iter()
+        .map(|(name, (flow_rate, _))| (*name, *flow_rate))
         .collect();
 
-    let paths = parsed
+    let paths: HashMap<_, _> = parsed
+        .into_iter()
         .map(|(name, (flow_rate, paths))| (name, (paths, flow_rate)))
         .collect();
 
-    InputData { paths, flow_rate }
+    if !paths.contains_key(&('A', 'A')) {
+        return Err(SolveError::new("missing starting valve AA"));
+    }
+    if paths
+        .values()
+        .any(|(tunnels, _)| tunnels.iter().any(|t| !paths.contains_key(t)))
+    {
+        return Err(SolveError::new("a tunnel leads to an unknown valve"));
+    }
+
+    Ok(InputData { paths, flow_rate })
 }
 
 fn get_released_pressure(node: &Node, input_data: &InputData) -> u16 {
@@ -271,7 +288,12 @@ fn both_moves(node: &Node, nodes: &mut NodeContainer, input_data: &InputData, us
     });
 }
 
-fn solve(input_data: &InputData, max_minute: u8, use_elephants: bool) -> u16 {
+fn solve(
+    input_data: &InputData,
+    max_minute: u8,
+    use_elephants: bool,
+    context: &PuzzleContext,
+) -> Result<u16, SolveError> {
     let mut nodes = NodeContainer::new();
 
     nodes.insert(get_starting_node());
@@ -279,6 +301,7 @@ fn solve(input_data: &InputData, max_minute: u8, use_elephants: bool) -> u16 {
     let mut max_flow_rate = 0;
 
     while let Some(node) = nodes.pop() {
+        context.check_cancelled()?;
         max_flow_rate = max_flow_rate.max(node.released_pressure);
 
         if node.minute == max_minute {
@@ -302,52 +325,191 @@ fn solve(input_data: &InputData, max_minute: u8, use_elephants: bool) -> u16 {
         both_moves(&node, &mut nodes, input_data, use_elephants);
     }
 
-    max_flow_rate
+    Ok(max_flow_rate)
+}
+
+fn puzzle_1(input_data: &InputData, context: &PuzzleContext) -> Result<Answer, SolveError> {
+    Ok(solve(input_data, 30, false, context)?.into())
+}
+
+fn puzzle_2(input_data: &InputData, context: &PuzzleContext) -> Result<Answer, SolveError> {
+    // This bruteforce solution takes a long time, the bitmask variant is much faster
+    // It stops when the runner cancels it
+
+    Ok(solve(input_data, 26, true, context)?.into())
+}
+
+// The valves with a flow, numbered for the bitmasks, and the minutes needed to reach and open them
+struct Network {
+    flow_rates: Vec<u16>,
+    // From each valve with a flow, then from AA at the last index
+    costs: Vec<Vec<u8>>,
+}
+
+fn distances_from(input_data: &InputData, start: ValveName) -> HashMap<ValveName, u8> {
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut queue = std::collections::VecDeque::from([start]);
+    while let Some(valve) = queue.pop_front() {
+        let distance = distances[&valve];
+        for next in &input_data.paths[&valve].0 {
+            if !distances.contains_key(next) {
+                distances.insert(*next, distance + 1);
+                queue.push_back(*next);
+            }
+        }
+    }
+    distances
+}
+
+fn get_network(input_data: &InputData) -> Network {
+    let valves = input_data
+        .flow_rate
+        .iter()
+        .filter(|(_, flow_rate)| *flow_rate > 0)
+        .copied()
+        .collect::<Vec<_>>();
+
+    let costs = valves
+        .iter()
+        .map(|(name, _)| *name)
+        .chain(std::iter::once(('A', 'A')))
+        .map(|start| {
+            let distances = distances_from(input_data, start);
+            valves
+                .iter()
+                // Unreachable valves cost more than the time available
+                .map(|(name, _)| distances.get(name).map_or(u8::MAX, |d| d + 1))
+                .collect()
+        })
+        .collect();
+
+    Network {
+        flow_rates: valves.iter().map(|(_, flow_rate)| *flow_rate).collect(),
+        costs,
+    }
+}
+
+// The highest pressure released by opening exactly the valves of each mask, in any order
+fn best_by_mask(
+    network: &Network,
+    max_minute: u8,
+    context: &PuzzleContext,
+) -> Result<Vec<u16>, SolveError> {
+    let start = network.flow_rates.len();
+    let mut best = vec![0; 1 << start];
+    // The valve just opened, the minutes left, the opened valves and the pressure they will release
+    let mut stack = vec![(start, max_minute, 0usize, 0u16)];
+
+    while let Some((valve, minutes_left, mask, pressure)) = stack.pop() {
+        context.check_cancelled()?;
+        best[mask] = best[mask].max(pressure);
+        for (next, &cost) in network.costs[valve].iter().enumerate() {
+            if mask & (1 << next) == 0 && cost < minutes_left {
+                let minutes_left = minutes_left - cost;
+                stack.push((
+                    next,
+                    minutes_left,
+                    mask | (1 << next),
+                    pressure + network.flow_rates[next] * minutes_left as u16,
+                ));
+            }
+        }
+    }
+    Ok(best)
+}
+
+fn puzzle_1_bitmask(input_data: &InputData, context: &PuzzleContext) -> Result<Answer, SolveError> {
+    let best = best_by_mask(&get_network(input_data), 30, context)?;
+    Ok(best.into_iter().max().unwrap_or(0).into())
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let input_data = parse_input(input);
+fn puzzle_2_bitmask(input_data: &InputData, context: &PuzzleContext) -> Result<Answer, SolveError> {
+    let network = get_network(input_data);
+    let mut best = best_by_mask(&network, 26, context)?;
+
+    // The best of each mask becomes the best of any of its subsets
+    for valve in 0..network.flow_rates.len() {
+        for mask in 0..best.len() {
+            if mask & (1 << valve) != 0 {
+                best[mask] = best[mask].max(best[mask ^ (1 << valve)]);
+            }
+        }
+    }
 
-    solve(&input_data, 30, false).to_string()
+    // The human and the elephant open disjoint sets of valves
+    let all = best.len() - 1;
+    let released = (0..best.len())
+        .map(|mask| best[mask] + best[all ^ mask])
+        .max()
+        .unwrap_or(0);
+    Ok(released.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let input_data = parse_input(input);
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = InputData;
+
+    const VARIANTS: &'static [Variant<Self>] = &[
+        Variant {
+            name: "bitmask",
+            puzzle_number: 1,
+            solve: puzzle_1_bitmask,
+        },
+        Variant {
+            name: "bitmask",
+            puzzle_number: 2,
+            solve: puzzle_2_bitmask,
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
 
-    // This bruteforce solution takes a long time, TODO: find a more clever solution
+    fn part_1(input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input, context)
+    }
 
-    solve(&input_data, 26, true).to_string()
+    fn part_2(input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input, context)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
-Valve BB has flow rate=13; tunnels lead to valves CC, AA
-Valve CC has flow rate=2; tunnels lead to valves DD, BB
-Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
-Valve EE has flow rate=3; tunnels lead to valves FF, DD
-Valve FF has flow rate=0; tunnels lead to valves EE, GG
-Valve GG has flow rate=0; tunnels lead to valves FF, HH
-Valve HH has flow rate=22; tunnel leads to valve GG
-Valve II has flow rate=0; tunnels lead to valves AA, JJ
-Valve JJ has flow rate=21; tunnel leads to valve II";
+    use crate::utils::fixtures::{assert_example, example_input};
 
     use super::*;
 
     #[test]
     fn test_parse_input() {
-        let parsed = parse_input(INPUT);
+        let parsed = parse_input(example_input(2022, 16, "example")).unwrap();
         let paths = parsed.paths.get(&('A', 'A')).unwrap();
         assert_eq!(paths, &(vec![('D', 'D'), ('I', 'I'), ('B', 'B')], 0),);
     }
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "1651");
+        assert_example::<Puzzle>(2022, 16, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "1707");
+        assert_example::<Puzzle>(2022, 16, "example", 2);
+    }
+
+    #[test]
+    fn test_bitmask() {
+        let input_data = parse_input(example_input(2022, 16, "example")).unwrap();
+        let context = PuzzleContext::default();
+        assert_eq!(
+            puzzle_1_bitmask(&input_data, &context),
+            Ok(Answer::Integer(1651))
+        );
+        assert_eq!(
+            puzzle_2_bitmask(&input_data, &context),
+            Ok(Answer::Integer(1707))
+        );
     }
 }
@@ -1,12 +1,15 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, HashMap};
 
-use crate::utils::geometry::{point2, Point2, Vector2};
+use crate::utils::{
+    geometry::{point2, Point2, Vector2},
+    parse_lines, Answer, Log, PuzzleContext, Solution, SolveError,
+};
 
 type Point = Point2<i32>;
 type Vector = Vector2<i32>;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Direction {
+pub enum Direction {
     Left,
     Right,
     Down,
@@ -31,6 +34,8 @@ impl Rock {
     }
 }
 
+type FallenRocks = BTreeMap<i32, Vec<(Point, Rock)>>;
+
 fn rock_intersects(rock_1: &Rock, position_1: Point, rock_2: &Rock, position_2: Point) -> bool {
     rock_1
         .parts
@@ -82,21 +87,30 @@ fn get_rock(number: u64) -> Rock {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Direction> {
-    input
-        .chars()
-        .filter_map(|c| match c {
-            '>' => Some(Direction::Right),
-            '<' => Some(Direction::Left),
-            _ => None,
-        })
-        .collect()
+fn parse_input(input: &str) -> Result<Vec<Direction>, SolveError> {
+    let directions: Vec<Direction> = parse_lines(input, |line| {
+        line.chars()
+            .map(|c| match c {
+                '>' => Ok(Direction::Right),
+                '<' => Ok(Direction::Left),
+                _ => Err(SolveError::new(format!("unknown jet direction {}", c))),
+            })
+            .collect::<Result<Vec<_>, _>>()
+    })?
+    .into_iter()
+    .flatten()
+    .collect();
+
+    match directions.is_empty() {
+        true => Err(SolveError::new("expected at least one jet")),
+        false => Ok(directions),
+    }
 }
 
 // Returns the new position, if the rock is able to move
 fn rock_moves(
     direction: Direction,
-    fallen_rocks: &BTreeMap<i32, Vec<(Point, Rock)>>,
+    fallen_rocks: &FallenRocks,
     rock: &Rock,
     position: Point,
 ) -> Option<Point> {
@@ -128,30 +142,31 @@ fn rock_moves(
     Some(new_position)
 }
 
-fn get_floor(fallen_rocks: &BTreeMap<i32, Vec<(Point, Rock)>>) -> u8 {
-    let &max_height = fallen_rocks.keys().max().unwrap();
+// Occupancy of the 8 rows below the highest point, one byte per row
+fn get_surface(fallen_rocks: &FallenRocks, highest_point: i32) -> u64 {
+    let lowest_row = highest_point - 8;
 
     fallen_rocks
-        .range(max_height - 6..=max_height)
+        .range(lowest_row..)
         .flat_map(|(_, v)| v)
         .flat_map(|(coord, rocks)| rocks.parts.iter().map(|r| *coord + *r))
-        .fold(0u8, |acc, coord| acc | (1u8 << (coord.x as u8)))
+        .filter(|coord| coord.y >= lowest_row)
+        .fold(0u64, |acc, coord| {
+            acc | (1u64 << ((highest_point - 1 - coord.y) * 8 + coord.x))
+        })
 }
 
-fn fall(
-    input: &Vec<Direction>,
-    rock_number: u64,
-) -> (BTreeMap<i32, Vec<(Point, Rock)>>, Option<(u64, i32, u64)>) {
+// Returns the tower height after each rock, and the first detected cycle as (start, length)
+fn fall(input: &[Direction], rock_number: u64) -> (Vec<i32>, Option<(u64, u64)>) {
     let mut highest_point = 0; // floor height
 
     let input_size = input.len();
 
     // Rocks indexed by their highest point
-    let mut fallen_rocks: BTreeMap<i32, Vec<(Point, Rock)>> = BTreeMap::new();
-
-    let mut cycle_history: BTreeMap<u8, BTreeSet<(usize, usize)>> = BTreeMap::new();
-    let mut floor_history: BTreeMap<u8, (u64, i32)> = BTreeMap::new();
+    let mut fallen_rocks: FallenRocks = BTreeMap::new();
 
+    let mut heights = vec![0];
+    let mut history: HashMap<(usize, u64, u64), u64> = HashMap::new();
     let mut cycle = None;
 
     let mut i: usize = 0;
@@ -182,66 +197,73 @@ fn fall(
                 break;
             }
         }
-        let floor = get_floor(&fallen_rocks);
-        let cycle_key = (i % input_size, (rock_number % 5) as usize);
-        let cycle_detected = match cycle_history.entry(floor) {
-            std::collections::btree_map::Entry::Vacant(entry) => {
-                entry.insert(BTreeSet::from([cycle_key]));
-                None
-            }
-            std::collections::btree_map::Entry::Occupied(mut entry) => {
-                match entry.get().contains(&cycle_key) {
-                    true => {
-                        let &last_floor_rock_number = floor_history.get(&floor).unwrap();
-                        Some((last_floor_rock_number, rock_number, cycle_key))
-                    }
-                    false => {
-                        entry.get_mut().insert(cycle_key);
-                        None
-                    }
-                }
-            }
-        };
-        floor_history.insert(floor, (rock_number, highest_point));
+        heights.push(highest_point);
+
         if cycle.is_none() {
-            cycle = cycle_detected.and_then(|(last, current, _key)| match floor == 127 {
-                true => Some((current - last.0, highest_point - last.1, current)),
-                false => None,
-            });
+            let state = (
+                i % input_size,
+                rock_number % 5,
+                get_surface(&fallen_rocks, highest_point),
+            );
+            if let Some(&last_rock_number) = history.get(&state) {
+                cycle = Some((last_rock_number, rock_number - last_rock_number));
+            } else {
+                history.insert(state, rock_number);
+            }
         }
     }
 
-    (fallen_rocks, cycle)
+    (heights, cycle)
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let input = parse_input(input);
-
-    (fall(&input, 2022).0.keys().max().unwrap() + 1).to_string()
+fn puzzle_1(input: &[Direction], rock_number: u64) -> Result<Answer, SolveError> {
+    Ok(fall(input, rock_number).0.last().unwrap().into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let input = parse_input(input);
-
+fn puzzle_2(input: &[Direction], log: &Log) -> Result<Answer, SolveError> {
     let max_len = 1_000_000_000_000;
 
     let mut initial_guess = 10;
-    let (length, height, start) = loop {
-        match fall(&input, initial_guess).1 {
-            Some((length, height, start)) => break (length, height, start),
-            None => (),
+    let (heights, (start, length)) = loop {
+        if let (heights, Some(cycle)) = fall(input, initial_guess) {
+            break (heights, cycle);
         }
         initial_guess *= 2;
     };
-    dbg!(length, height, start);
+    log.debug(format!("cycle of {} rocks after {} rocks", length, start));
+
+    let cycle_height = (heights[(start + length) as usize] - heights[start as usize]) as u64;
     let remaining_cycles = (max_len - start) / length;
-    let result = remaining_cycles * height as u64;
-    result.to_string()
+    let remaining_rocks = (max_len - start) % length;
+
+    let result =
+        heights[(start + remaining_rocks) as usize] as u64 + remaining_cycles * cycle_height;
+    Ok(result.into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Direction>;
+
+    const PARAMS: &'static [(&'static str, &'static str)] = &[("rocks", "2022")];
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input, context.param("rocks")?)
+    }
+
+    fn part_2(input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input, context.log())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
@@ -251,17 +273,17 @@ mod tests {
         let parsed = parse_input(input);
         assert_eq!(
             parsed,
-            vec![Direction::Left, Direction::Right, Direction::Left]
+            Ok(vec![Direction::Left, Direction::Right, Direction::Left])
         );
     }
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "3068");
+        assert_example::<Puzzle>(2022, 17, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "1514285714288");
+        assert_example::<Puzzle>(2022, 17, "example", 2);
     }
 }
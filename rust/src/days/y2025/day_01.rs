@@ -1,29 +1,26 @@
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     distance: i16,
 }
 
-fn load_input(input: &str) -> Vec<Input> {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|l| l.split_at(1))
-        .map(|(direction, distance)| {
-            let direction = match direction.chars().next().unwrap() {
-                'L' => -1,
-                'R' => 1,
-                _ => panic!("Unhandled"),
-            };
-            let distance = distance.parse::<i16>().unwrap();
-            Input {
-                distance: direction * distance,
-            }
+fn load_input(input: &str) -> Result<Vec<Input>, SolveError> {
+    parse_lines(input, |l| {
+        let (direction, distance) = l.split_at_checked(1).ok_or("expected a rotation")?;
+        let direction = match direction {
+            "L" => -1,
+            "R" => 1,
+            _ => return Err(SolveError::new("unknown rotation direction")),
+        };
+        let distance = distance.parse::<i16>()?;
+        Ok(Input {
+            distance: direction * distance,
         })
-        .collect()
+    })
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let input = load_input(input);
+fn puzzle_1(input: &[Input]) -> Result<Answer, SolveError> {
     let mut dial = 50;
     let mut res = 0;
     for code in input {
@@ -33,11 +30,10 @@ pub fn puzzle_1(input: &str) -> String {
         }
     }
 
-    res.to_string()
+    Ok(res.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let input = load_input(input);
+fn puzzle_2(input: &[Input]) -> Result<Answer, SolveError> {
     let mut dial = 50;
     let mut res = 0;
     for code in input {
@@ -54,32 +50,40 @@ pub fn puzzle_2(input: &str) -> String {
         dial %= 100;
     }
 
-    res.to_string()
+    Ok(res.into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Input>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
-    const INPUT: &str = "L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "3");
+        assert_example::<Puzzle>(2025, 1, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "6");
+        assert_example::<Puzzle>(2025, 1, "example", 2);
     }
 }
@@ -1,10 +1,17 @@
-fn load_input(input: &str) -> Vec<(i64, i64)> {
+use crate::utils::{Answer, PuzzleContext, Solution, SolveError};
+
+fn load_input(input: &str) -> Result<Vec<(i64, i64)>, SolveError> {
+    let input = input.trim();
     input
-        .trim()
         .split(',')
-        .map(|str| str.split_once('-').unwrap())
-        .map(|(l, r)| (l.parse().unwrap(), r.parse().unwrap()))
-        .collect()
+        .map(|range| {
+            let (l, r) = range
+                .split_once('-')
+                .ok_or_else(|| SolveError::new(format!("expected a range, got {}", range)))?;
+            Ok((l.parse()?, r.parse()?))
+        })
+        .collect::<Result<_, SolveError>>()
+        .map_err(|e| e.at_line(1, input))
 }
 
 fn is_valid_n(number: i64) -> bool {
@@ -34,48 +41,63 @@ fn is_valid_1(number: i64) -> bool {
     l != r
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let input = load_input(input);
+fn puzzle_1(input: &[(i64, i64)]) -> Result<Answer, SolveError> {
     let mut res: i64 = 0;
 
-    for (l, r) in input {
+    for &(l, r) in input {
         for i in l..=r {
             if !is_valid_1(i) {
                 res += i;
             }
         }
     }
-    res.to_string()
+    Ok(res.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let input = load_input(input);
+fn puzzle_2(input: &[(i64, i64)]) -> Result<Answer, SolveError> {
     let mut res: i64 = 0;
 
-    for (l, r) in input {
+    for &(l, r) in input {
         for i in l..=r {
             if !is_valid_n(i) {
                 res += i;
             }
         }
     }
-    res.to_string()
+    Ok(res.into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<(i64, i64)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
-    const INPUT: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "1227775554");
+        assert_example::<Puzzle>(2025, 2, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "4174379265");
+        assert_example::<Puzzle>(2025, 2, "example", 2);
     }
 }
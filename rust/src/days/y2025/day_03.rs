@@ -1,9 +1,14 @@
-fn load_input(input: &str) -> Vec<Vec<u32>> {
-    input
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|l| l.chars().map(|c| c.to_digit(10).unwrap()).collect())
-        .collect()
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
+fn load_input(input: &str) -> Result<Vec<Vec<u32>>, SolveError> {
+    parse_lines(input, |l| {
+        l.chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .ok_or_else(|| SolveError::new(format!("invalid joltage {}", c)))
+            })
+            .collect()
+    })
 }
 
 fn largest_joltage(bank: &[u32], batteries: usize) -> u64 {
@@ -22,35 +27,60 @@ fn largest_joltage(bank: &[u32], batteries: usize) -> u64 {
     res
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let input = load_input(input);
-    let res: u64 = input.iter().map(|bank| largest_joltage(bank, 2)).sum();
-    res.to_string()
+fn total_joltage(input: &[Vec<u32>], batteries: usize) -> Result<Answer, SolveError> {
+    if input.iter().any(|bank| bank.len() < batteries) {
+        return Err(SolveError::new(format!(
+            "every bank needs at least {} batteries",
+            batteries
+        )));
+    }
+
+    let res: u64 = input
+        .iter()
+        .map(|bank| largest_joltage(bank, batteries))
+        .sum();
+    Ok(res.into())
+}
+
+fn puzzle_1(input: &[Vec<u32>]) -> Result<Answer, SolveError> {
+    total_joltage(input, 2)
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let input = load_input(input);
-    let res: u64 = input.iter().map(|bank| largest_joltage(bank, 12)).sum();
-    res.to_string()
+fn puzzle_2(input: &[Vec<u32>]) -> Result<Answer, SolveError> {
+    total_joltage(input, 12)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Vec<u32>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
-    const INPUT: &str = "987654321111111
-811111111111119
-234234234234278
-818181911112111";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "357");
+        assert_example::<Puzzle>(2025, 3, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "3121910778619");
+        assert_example::<Puzzle>(2025, 3, "example", 2);
     }
 }
@@ -1,12 +1,22 @@
 use std::collections::BTreeSet;
 
-use crate::utils::geometry::{point2, Point2};
+use crate::utils::{
+    geometry::{point2, Point2},
+    parse_lines, Answer, PuzzleContext, Solution, SolveError,
+};
 
 type Point = Point2<i16>;
 
-fn load_input(input: &str) -> BTreeSet<Point> {
-    input
-        .lines()
+fn load_input(input: &str) -> Result<BTreeSet<Point>, SolveError> {
+    let lines = parse_lines(input, |line| {
+        match line.chars().all(|c| c == '@' || c == '.') {
+            true => Ok(line.to_string()),
+            false => Err(SolveError::new("expected only @ and . cells")),
+        }
+    })?;
+
+    Ok(lines
+        .iter()
         .enumerate()
         .flat_map(|(i, line)| {
             line.chars().enumerate().filter_map(move |(j, c)| match c {
@@ -17,7 +27,7 @@ fn load_input(input: &str) -> BTreeSet<Point> {
                 _ => None,
             })
         })
-        .collect()
+        .collect())
 }
 
 fn has_enough_neighbours(point: Point, map: &BTreeSet<Point>, limit: usize) -> bool {
@@ -37,17 +47,16 @@ fn has_enough_neighbours(point: Point, map: &BTreeSet<Point>, limit: usize) -> b
         >= limit
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let input = load_input(input);
+fn puzzle_1(input: &BTreeSet<Point>) -> Result<Answer, SolveError> {
     let res = input
         .iter()
-        .filter(|&&point| !has_enough_neighbours(point, &input, 4))
+        .filter(|&&point| !has_enough_neighbours(point, input, 4))
         .count();
-    res.to_string()
+    Ok(res.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let mut input = load_input(input);
+fn puzzle_2(input: &BTreeSet<Point>) -> Result<Answer, SolveError> {
+    let mut input = input.clone();
     let mut count = 0;
     loop {
         let accessible = input
@@ -66,32 +75,40 @@ pub fn puzzle_2(input: &str) -> String {
         }
     }
 
-    count.to_string()
+    Ok(count.into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = BTreeSet<Point>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
-    const INPUT: &str = "..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@.";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "13");
+        assert_example::<Puzzle>(2025, 4, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "43");
+        assert_example::<Puzzle>(2025, 4, "example", 2);
     }
 }
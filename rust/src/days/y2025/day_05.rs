@@ -1,40 +1,34 @@
 use std::{collections::BTreeSet, ops::RangeInclusive};
 
-fn load_input(input: &str) -> (Vec<RangeInclusive<usize>>, Vec<usize>) {
-    let (ranges, ingredients) = input.split_once("\n\n").unwrap();
-
-    (
-        ranges
-            .lines()
-            .map(|l| {
-                let (min, max) = l.split_once('-').unwrap();
-                min.parse().unwrap()..=max.parse().unwrap()
-            })
-            .collect(),
-        ingredients
-            .trim()
-            .lines()
-            .map(|l| l.parse().unwrap())
-            .collect(),
-    )
-}
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
+fn load_input(input: &str) -> Result<(Vec<RangeInclusive<usize>>, Vec<usize>), SolveError> {
+    let (ranges, ingredients) = input.split_once("\n\n").ok_or("missing ingredients")?;
 
-pub fn puzzle_1(input: &str) -> String {
-    let input = load_input(input);
+    let offset = ranges.lines().count() + 1;
 
+    let ranges = parse_lines(ranges, |l| {
+        let (min, max) = l.split_once('-').ok_or("expected a range")?;
+        Ok(min.parse()?..=max.parse()?)
+    })?;
+    let ingredients =
+        parse_lines(ingredients, |l| Ok(l.parse()?)).map_err(|e| e.shift_line(offset))?;
+
+    Ok((ranges, ingredients))
+}
+
+fn puzzle_1(input: &(Vec<RangeInclusive<usize>>, Vec<usize>)) -> Result<Answer, SolveError> {
     let res = input
         .1
         .iter()
         .filter(|&id| input.0.iter().any(|r| r.contains(id)))
         .count();
 
-    res.to_string()
+    Ok(res.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let input = load_input(input);
-
-    let mut ranges = input.0;
+fn puzzle_2(input: &(Vec<RangeInclusive<usize>>, Vec<usize>)) -> Result<Answer, SolveError> {
+    let mut ranges = input.0.clone();
 
     loop {
         let extended_ranges = ranges
@@ -81,33 +75,40 @@ pub fn puzzle_2(input: &str) -> String {
         .map(|(s, e)| e - s + 1)
         .sum::<usize>();
 
-    res.to_string()
+    Ok(res.into())
 }
 
-#[cfg(test)]
-mod tests {
+pub struct Puzzle;
 
-    const INPUT: &str = "3-5
-10-14
-16-20
-12-18
+impl Solution for Puzzle {
+    type Parsed = (Vec<RangeInclusive<usize>>, Vec<usize>);
 
-1
-5
-8
-11
-17
-32";
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "3");
+        assert_example::<Puzzle>(2025, 5, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "14");
+        assert_example::<Puzzle>(2025, 5, "example", 2);
     }
 }
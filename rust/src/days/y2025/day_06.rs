@@ -1,17 +1,22 @@
-use core::panic;
 use std::iter::once;
 
+use crate::utils::{Answer, PuzzleContext, Solution, SolveError};
+
 #[derive(Clone, Copy, Debug)]
-enum Operator {
+pub enum Operator {
     Add,
     Mul,
 }
 
-fn load_input(input: &str) -> Vec<(Operator, Vec<String>)> {
+fn load_input(input: &str) -> Result<Vec<(Operator, Vec<String>)>, SolveError> {
+    if !input.is_ascii() {
+        return Err(SolveError::new("expected an ascii worksheet"));
+    }
+
     let indexes = input
         .lines()
-        .next_back()
-        .unwrap()
+        .rfind(|l| !l.is_empty())
+        .ok_or("missing operators")?
         .chars()
         .enumerate()
         .filter(|&(_i, c)| c == '+' || c == '*')
@@ -20,8 +25,7 @@ fn load_input(input: &str) -> Vec<(Operator, Vec<String>)> {
                 i,
                 match c {
                     '*' => Operator::Mul,
-                    '+' => Operator::Add,
-                    _ => panic!(),
+                    _ => Operator::Add,
                 },
             )
         });
@@ -35,21 +39,25 @@ fn load_input(input: &str) -> Vec<(Operator, Vec<String>)> {
         .into_iter()
         .skip(1)
         .map(|(i, _c)| i)
-        .chain(once(input.lines().next().unwrap().len() + 1));
+        .chain(once(input.lines().next().unwrap_or_default().len() + 1));
 
-    input
+    for (line_number, l) in input
         .lines()
         .take_while(|l| !(l.contains('*') || l.contains('+')))
-        .for_each(|l| {
-            let mut r = result.iter_mut();
-            let mut last_i = 0;
-            for i in split.clone() {
-                r.next().unwrap().1.push(l[last_i..i - 1].to_string());
-                last_i = i;
-            }
-        });
+        .enumerate()
+    {
+        let mut r = result.iter_mut();
+        let mut last_i = 0;
+        for i in split.clone() {
+            let operand = l.get(last_i..i - 1).ok_or_else(|| {
+                SolveError::new("the line is shorter than the others").at_line(line_number + 1, l)
+            })?;
+            r.next().unwrap().1.push(operand.to_string());
+            last_i = i;
+        }
+    }
 
-    result
+    Ok(result)
 }
 
 fn apply(operation: Operator, operands: &[usize]) -> usize {
@@ -65,12 +73,12 @@ fn apply(operation: Operator, operands: &[usize]) -> usize {
     )
 }
 
-fn to_normal_math(operands: &[String]) -> Vec<usize> {
-    operands.iter().map(|s| s.trim().parse().unwrap()).collect()
+fn to_normal_math(operands: &[String]) -> Result<Vec<usize>, SolveError> {
+    operands.iter().map(|s| Ok(s.trim().parse()?)).collect()
 }
 
-fn to_cephalopods_math(operands: &[String]) -> Vec<usize> {
-    let max_len = operands.iter().map(|s| s.len()).max().unwrap();
+fn to_cephalopods_math(operands: &[String]) -> Result<Vec<usize>, SolveError> {
+    let max_len = operands.iter().map(|s| s.len()).max().unwrap_or_default();
     let mut cephalopods_numbers = vec![];
     cephalopods_numbers.resize(max_len, String::new());
 
@@ -81,51 +89,65 @@ fn to_cephalopods_math(operands: &[String]) -> Vec<usize> {
     });
     cephalopods_numbers
         .iter()
-        .map(|str| str.trim().parse().unwrap())
+        .map(|str| Ok(str.trim().parse()?))
         .collect()
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let res = load_input(input)
+fn puzzle_1(input: &[(Operator, Vec<String>)]) -> Result<Answer, SolveError> {
+    let res = input
         .iter()
         .map(|(operator, operands)| {
-            let operands = to_normal_math(operands);
-            apply(*operator, &operands)
+            let operands = to_normal_math(operands)?;
+            Ok(apply(*operator, &operands))
         })
-        .sum::<usize>();
+        .sum::<Result<usize, SolveError>>()?;
 
-    res.to_string()
+    Ok(res.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let res = load_input(input)
+fn puzzle_2(input: &[(Operator, Vec<String>)]) -> Result<Answer, SolveError> {
+    let res = input
         .iter()
         .map(|(operator, operands)| {
-            let operands = to_cephalopods_math(operands);
-            apply(*operator, &operands)
+            let operands = to_cephalopods_math(operands)?;
+            Ok(apply(*operator, &operands))
         })
-        .sum::<usize>();
+        .sum::<Result<usize, SolveError>>()?;
+
+    Ok(res.into())
+}
 
-    res.to_string()
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<(Operator, Vec<String>)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
-    const INPUT: &str = "123 328  51 64 
- 45 64  387 23 
-  6 98  215 314
-*   +   *   +  ";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "4277556");
+        assert_example::<Puzzle>(2025, 6, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "3263827");
+        assert_example::<Puzzle>(2025, 6, "example", 2);
     }
 }
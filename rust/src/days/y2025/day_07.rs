@@ -1,34 +1,52 @@
-fn load_input(input: &str) -> (usize, impl Iterator<Item = Vec<usize>> + use<'_>) {
-    (
-        input
-            .lines()
-            .next()
-            .unwrap()
-            .chars()
-            .position(|c| c == 'S')
-            .unwrap(),
-        input.lines().skip(1).map(|l| {
-            l.char_indices()
-                .filter_map(|(i, c)| match c {
-                    '^' => Some(i),
-                    _ => None,
-                })
-                .collect()
-        }),
-    )
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
+pub struct Manifold {
+    start: usize,
+    width: usize,
+    splitters: Vec<Vec<usize>>,
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let (start, splitters) = load_input(input);
+fn load_input(input: &str) -> Result<Manifold, SolveError> {
+    let first_line = input.lines().next().ok_or("empty manifold")?;
+    let start = first_line
+        .chars()
+        .position(|c| c == 'S')
+        .ok_or_else(|| SolveError::new("missing start").at_line(1, first_line))?;
+    let width = first_line.len();
 
-    let width = input.lines().next().unwrap().len();
+    let splitters = parse_lines(input, |l| {
+        if l.len() != width {
+            return Err(SolveError::new("every row must have the same width"));
+        }
+        l.char_indices()
+            .filter_map(|(i, c)| match c {
+                '^' if i == 0 || i == width - 1 => {
+                    Some(Err(SolveError::new("a splitter cannot be on the edge")))
+                }
+                '^' => Some(Ok(i)),
+                _ => None,
+            })
+            .collect()
+    })?;
+
+    Ok(Manifold {
+        start,
+        width,
+        splitters: splitters.into_iter().skip(1).collect(),
+    })
+}
+
+fn puzzle_1(manifold: &Manifold) -> Result<Answer, SolveError> {
+    let (start, width) = (manifold.start, manifold.width);
     let rays = {
         let mut res = vec![false; width];
         res[start] = true;
         res
     };
 
-    let res: usize = splitters
+    let res: usize = manifold
+        .splitters
+        .iter()
         .scan(rays, |rays, splitters| {
             let mut count = 0;
             let mut new_rays = vec![false; width];
@@ -48,20 +66,18 @@ pub fn puzzle_1(input: &str) -> String {
         })
         .sum();
 
-    res.to_string()
+    Ok(res.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let (start, splitters) = load_input(input);
-
-    let width = input.lines().next().unwrap().len();
+fn puzzle_2(manifold: &Manifold) -> Result<Answer, SolveError> {
+    let (start, width) = (manifold.start, manifold.width);
     let rays = {
         let mut res = vec![0usize; width];
         res[start] = 1;
         res
     };
 
-    let rays = splitters.fold(rays, |rays, splitters| {
+    let rays = manifold.splitters.iter().fold(rays, |rays, splitters| {
         let mut new_rays = vec![0usize; width];
         for (i, ray_count) in rays.iter().enumerate() {
             if splitters.contains(&i) {
@@ -75,39 +91,40 @@ pub fn puzzle_2(input: &str) -> String {
     });
 
     let res: usize = rays.iter().sum();
-    res.to_string()
+    Ok(res.into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Manifold;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
-    const INPUT: &str = ".......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-...............
-";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "21");
+        assert_example::<Puzzle>(2025, 7, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "40");
+        assert_example::<Puzzle>(2025, 7, "example", 2);
     }
 }
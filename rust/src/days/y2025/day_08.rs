@@ -1,22 +1,26 @@
 use std::collections::BTreeSet;
 
+use crate::utils::{parse_lines, Answer, PuzzleContext, Solution, SolveError};
+
 #[derive(Clone, Copy, Debug)]
-struct Position {
+pub struct Position {
     x: u64,
     y: u64,
     z: u64,
 }
 
-fn load_input(input: &str) -> Vec<Position> {
-    input
-        .lines()
-        .map(|line| line.split(','))
-        .map(|mut p| Position {
-            x: p.next().unwrap().parse().unwrap(),
-            y: p.next().unwrap().parse().unwrap(),
-            z: p.next().unwrap().parse().unwrap(),
+fn load_input(input: &str) -> Result<Vec<Position>, SolveError> {
+    parse_lines(input, |line| {
+        let mut p = line.split(',');
+        let mut coordinate = || -> Result<u64, SolveError> {
+            Ok(p.next().ok_or("expected a position as x,y,z")?.parse()?)
+        };
+        Ok(Position {
+            x: coordinate()?,
+            y: coordinate()?,
+            z: coordinate()?,
         })
-        .collect()
+    })
 }
 
 fn distance_sq(lhs: &Position, rhs: &Position) -> u64 {
@@ -46,10 +50,12 @@ fn add_to_groups(mut groups: Vec<BTreeSet<u16>>, c: (u16, u16)) -> Vec<BTreeSet<
         .enumerate()
         .find(|(_i, r)| r.contains(&c.1))
         .map(|p| p.0);
-    if g0.is_some() && g1.is_some() && g0.unwrap() != g1.unwrap() {
-        let mut to_cpy = groups[g1.unwrap()].clone();
-        groups[g0.unwrap()].append(&mut to_cpy);
-        groups.remove(g1.unwrap());
+    if let (Some(g0), Some(g1)) = (g0, g1) {
+        if g0 != g1 {
+            let mut to_cpy = groups[g1].clone();
+            groups[g0].append(&mut to_cpy);
+            groups.remove(g1);
+        }
     }
     let group = if let Some(group) = groups
         .iter_mut()
@@ -66,12 +72,8 @@ fn add_to_groups(mut groups: Vec<BTreeSet<u16>>, c: (u16, u16)) -> Vec<BTreeSet<
     groups
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let input = load_input(input);
-    let is_test = input.len() == 20;
-
-    let mut distances = get_distances(&input).collect::<Vec<_>>();
-    let pair_count = if is_test { 10 } else { 1000 };
+fn puzzle_1(input: &[Position], pair_count: usize) -> Result<Answer, SolveError> {
+    let mut distances = get_distances(input).collect::<Vec<_>>();
     distances.sort_by_key(|p| p.1);
     let groups = distances
         .iter()
@@ -86,64 +88,63 @@ pub fn puzzle_1(input: &str) -> String {
         .rev()
         .take(3)
         .reduce(|acc, x| acc * x)
-        .unwrap();
+        .ok_or("expected at least two junction boxes")?;
 
-    res.to_string()
+    Ok(res.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let input = load_input(input);
-
-    let mut distances = get_distances(&input).collect::<Vec<_>>();
+fn puzzle_2(input: &[Position]) -> Result<Answer, SolveError> {
+    let mut distances = get_distances(input).collect::<Vec<_>>();
     distances.sort_by_key(|p| p.1);
 
     let mut groups = vec![];
-    let mut breaking_connection = (0, 0);
+    let mut breaking_connection = None;
     for (c, _d) in distances {
         groups = add_to_groups(groups, c);
         if groups[0].len() == input.len() {
-            breaking_connection = c;
+            breaking_connection = Some(c);
             break;
         }
     }
 
+    let breaking_connection = breaking_connection.ok_or("expected at least two junction boxes")?;
     let res = input[breaking_connection.0 as usize].x * input[breaking_connection.1 as usize].x;
-    res.to_string()
+    Ok(res.into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Position>;
+
+    const PARAMS: &'static [(&'static str, &'static str)] = &[("pairs", "1000")];
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input, context.param("pairs")?)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
-    const INPUT: &str = "162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "40");
+        assert_example::<Puzzle>(2025, 8, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "25272");
+        assert_example::<Puzzle>(2025, 8, "example", 2);
     }
 }
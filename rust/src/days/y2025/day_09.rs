@@ -1,18 +1,23 @@
 use rayon::prelude::*;
 
-use crate::utils::geometry::{point2, Line2, Point2};
+use crate::utils::{
+    geometry::{point2, Line2, Point2},
+    parse_lines, Answer, PuzzleContext, Solution, SolveError,
+};
 
 type Point = Point2<i64>;
 type Line = Line2<i64>;
 
-fn load_input(input: &str) -> Vec<Point> {
-    input
-        .lines()
-        .filter_map(|l| {
-            l.split_once(',')
-                .map(|(x, y)| point2!(x.parse().unwrap(), y.parse().unwrap()))
-        })
-        .collect()
+fn load_input(input: &str) -> Result<Vec<Point>, SolveError> {
+    let points = parse_lines(input, |l| {
+        let (x, y) = l.split_once(',').ok_or("expected a point as x,y")?;
+        Ok(point2!(x.parse()?, y.parse()?))
+    })?;
+
+    match points.is_empty() {
+        true => Err(SolveError::new("expected at least one red tile")),
+        false => Ok(points),
+    }
 }
 
 fn area(lhs: &Point, rhs: &Point) -> u64 {
@@ -72,23 +77,19 @@ fn is_totally_inside(point_a: &Point, point_b: &Point, multiline: &[Point]) -> b
         .all(|l| l.into_iter().par_bridge().all(|p| is_inside(&p, multiline)))
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let input = load_input(input);
-
+fn puzzle_1(input: &[Point]) -> Result<Answer, SolveError> {
     let res = input
         .iter()
         .flat_map(|a| input.iter().map(|b| area(a, b)))
         .max()
         .unwrap();
 
-    res.to_string()
+    Ok(res.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let input = load_input(input);
-
+fn puzzle_2(input: &[Point]) -> Result<Answer, SolveError> {
     let perimeter = {
-        let mut res = input.clone();
+        let mut res = input.to_vec();
         res.push(*input.first().unwrap());
         res
     };
@@ -106,29 +107,39 @@ pub fn puzzle_2(input: &str) -> String {
             }
         });
 
-    res.to_string()
+    Ok(res.into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Point>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
-    const INPUT: &str = "7,1
-11,1
-11,7
-9,7
-9,5
-2,5
-2,3
-7,3";
+    use crate::utils::fixtures::{assert_example, example_input};
 
     use super::*;
 
     #[test]
     fn test_is_inside() {
-        let input = load_input("0,0\n12,0\n12,4\n4,4\n4,8\n8,8\n8,4\n12,4\n12,12\n0,12");
+        let input = load_input("0,0\n12,0\n12,4\n4,4\n4,8\n8,8\n8,4\n12,4\n12,12\n0,12").unwrap();
         let perimeter = {
-            let mut res = input.clone();
-            res.push(input.first().unwrap().clone());
+            let mut res = input.to_vec();
+            res.push(*input.first().unwrap());
             res
         };
 
@@ -149,11 +160,11 @@ mod tests {
             &perimeter
         ));
 
-        let input = load_input(INPUT);
+        let input = load_input(example_input(2025, 9, "example")).unwrap();
 
         let perimeter = {
-            let mut res = input.clone();
-            res.push(input.first().unwrap().clone());
+            let mut res = input.to_vec();
+            res.push(*input.first().unwrap());
             res
         };
 
@@ -201,11 +212,11 @@ mod tests {
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "50");
+        assert_example::<Puzzle>(2025, 9, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "24");
+        assert_example::<Puzzle>(2025, 9, "example", 2);
     }
 }
@@ -1,51 +1,63 @@
-use rayon::prelude::*;
 use std::collections::BTreeMap;
 
+use crate::utils::{parse_lines, Answer, Log, PuzzleContext, Solution, SolveError};
+
 #[derive(Debug)]
-struct Machine {
+pub struct Machine {
     lights: Vec<bool>,
     buttons: Vec<Vec<usize>>,
     joltage: Vec<u32>,
 }
 
-fn load_input(input: &str) -> Vec<Machine> {
-    input
-        .lines()
-        .map(|line| {
-            let mut blocks = line.split(' ');
-            let lights = blocks.next().unwrap();
-            let buttons = blocks.take_while(|s| s.chars().next().unwrap() == '(');
-            let joltage = line.split(' ').last().unwrap();
-
-            let lights = lights
-                .trim_matches(|c| c == '[' || c == ']')
-                .chars()
-                .map(|c| c == '#')
-                .collect::<Vec<_>>();
-
-            let buttons = buttons
-                .map(|button| {
-                    button
-                        .trim_matches(|c| c == '(' || c == ')')
-                        .split(',')
-                        .map(|c| c.parse().unwrap())
-                        .collect::<Vec<_>>()
-                })
-                .collect::<Vec<_>>();
-
-            let joltage = joltage
-                .trim_matches(|c| c == '{' || c == '}')
-                .split(',')
-                .map(|c| c.parse().unwrap())
-                .collect::<Vec<_>>();
-
-            Machine {
-                lights,
-                buttons,
-                joltage,
-            }
+fn load_input(input: &str) -> Result<Vec<Machine>, SolveError> {
+    parse_lines(input, |line| {
+        let mut blocks = line.split(' ');
+        let lights = blocks.next().unwrap_or_default();
+        let buttons = blocks.take_while(|s| s.starts_with('('));
+        let joltage = line.split(' ').next_back().unwrap_or_default();
+
+        if !(lights.starts_with('[') && joltage.starts_with('{')) {
+            return Err(SolveError::new("expected lights, buttons and joltage"));
+        }
+
+        let lights = lights
+            .trim_matches(|c| c == '[' || c == ']')
+            .chars()
+            .map(|c| c == '#')
+            .collect::<Vec<_>>();
+
+        let buttons = buttons
+            .map(|button| {
+                button
+                    .trim_matches(|c| c == '(' || c == ')')
+                    .split(',')
+                    .map(|c| match c.parse()? {
+                        i if i < lights.len() => Ok(i),
+                        i => Err(SolveError::new(format!(
+                            "button toggles unknown light {}",
+                            i
+                        ))),
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let joltage = joltage
+            .trim_matches(|c| c == '{' || c == '}')
+            .split(',')
+            .map(|c| c.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if joltage.len() != lights.len() {
+            return Err(SolveError::new("expected one joltage per light"));
+        }
+
+        Ok(Machine {
+            lights,
+            buttons,
+            joltage,
         })
-        .collect()
+    })
 }
 
 fn press_buttons_for_lights(mut lights: Vec<bool>, button: &[usize]) -> Vec<bool> {
@@ -91,6 +103,7 @@ fn search_buttons_for_lights(machine: &Machine) -> usize {
     min_presses
 }
 
+#[allow(dead_code)]
 fn joltage_all_buttons(machine: &Machine) -> Vec<u32> {
     let mut joltage = vec![0; machine.joltage.len()];
     for button in &machine.buttons {
@@ -104,7 +117,8 @@ fn search_buttons_for_joltage(machine: &Machine) -> u32 {
 
     let mut states = BTreeMap::from([(0u32, vec![(0u32, vec![0; machine.joltage.len()])])]);
 
-    let range = (0..=machine.joltage.iter().max().unwrap().ilog2()).map(|n| 2u32.pow(n));
+    let max_joltage = machine.joltage.iter().max().copied().unwrap_or_default();
+    let range = (0..=max_joltage.max(1).ilog2()).map(|n| 2u32.pow(n));
     let min_presses = loop {
         let mut entry = states.first_entry().unwrap();
         let cost = *entry.key();
@@ -136,49 +150,54 @@ fn search_buttons_for_joltage(machine: &Machine) -> u32 {
     min_presses
 }
 
-pub fn puzzle_1(input: &str) -> String {
-    let input = load_input(input);
+fn puzzle_1(input: &[Machine]) -> Result<Answer, SolveError> {
+    let res: usize = input.iter().map(search_buttons_for_lights).sum();
 
-    let res: usize = input
+    Ok(res.into())
+}
+
+fn puzzle_2(input: &[Machine], log: &Log) -> Result<Answer, SolveError> {
+    let progress = log.progress("machines", input.len());
+    let res: u32 = input
         .iter()
-        .map(|machine| search_buttons_for_lights(machine))
+        .map(search_buttons_for_joltage)
+        .inspect(|_| progress.tick())
         .sum();
 
-    res.to_string()
+    Ok(res.into())
 }
 
-pub fn puzzle_2(input: &str) -> String {
-    let input = load_input(input);
+pub struct Puzzle;
 
-    let mut i = 0;
-    let res: u32 = input
-        .iter()
-        .inspect(|_| {
-            println!("{}", i);
-            i += 1;
-        })
-        .map(|machine| search_buttons_for_joltage(machine))
-        .sum();
+impl Solution for Puzzle {
+    type Parsed = Vec<Machine>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_1(input)
+    }
 
-    res.to_string()
+    fn part_2(input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError> {
+        puzzle_2(input, context.log())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
-    const INPUT: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
-[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
-[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+    use crate::utils::fixtures::assert_example;
 
     use super::*;
 
     #[test]
     fn test_puzzle_1() {
-        assert_eq!(puzzle_1(INPUT), "7");
+        assert_example::<Puzzle>(2025, 10, "example", 1);
     }
 
     #[test]
     fn test_puzzle_2() {
-        assert_eq!(puzzle_2(INPUT), "33");
+        assert_example::<Puzzle>(2025, 10, "example", 2);
     }
 }
@@ -0,0 +1,382 @@
+use std::time::Duration;
+
+use avc::utils::{bench::Stats, memory::AllocStats, Execution, SolveError};
+
+use crate::{
+    bench::{BenchOutcome, BenchRun},
+    summary::{DayOutcome, DayRun},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Integer(i128),
+    Text(String),
+    // Nested records, an array of objects in JSON and the same array in a CSV cell
+    List(Vec<Record>),
+}
+
+impl From<Duration> for Value {
+    fn from(duration: Duration) -> Self {
+        Value::Integer(duration.as_nanos() as i128)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Value::Null)
+    }
+}
+
+impl From<u16> for Value {
+    fn from(n: u16) -> Self {
+        Value::Integer(n as i128)
+    }
+}
+
+impl From<u8> for Value {
+    fn from(n: u8) -> Self {
+        Value::Integer(n as i128)
+    }
+}
+
+impl From<u64> for Value {
+    fn from(n: u64) -> Self {
+        Value::Integer(n as i128)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(n: usize) -> Self {
+        Value::Integer(n as i128)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Self {
+        Value::Text(text.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        Value::Text(text)
+    }
+}
+
+// Every record of an export has the same fields in the same order, so that they fit in CSV columns
+pub type Record = Vec<(&'static str, Value)>;
+
+pub fn json_string(text: &str) -> String {
+    let mut escaped = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_value(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Integer(n) => n.to_string(),
+        Value::Text(text) => json_string(text),
+        Value::List(records) => {
+            let objects = records.iter().map(json_object).collect::<Vec<_>>();
+            format!("[{}]", objects.join(", "))
+        }
+    }
+}
+
+fn json_object(record: &Record) -> String {
+    let fields = record
+        .iter()
+        .map(|(key, value)| format!("{}: {}", json_string(key), json_value(value)))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", fields.join(", "))
+}
+
+// One object per line, in an array
+pub fn to_json(records: &[Record]) -> String {
+    if records.is_empty() {
+        return "[]".to_string();
+    }
+    let objects = records
+        .iter()
+        .map(|record| format!("  {}", json_object(record)))
+        .collect::<Vec<_>>();
+    format!("[\n{}\n]", objects.join(",\n"))
+}
+
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Integer(n) => n.to_string(),
+        Value::Text(text) if text.contains([',', '"', '\n', '\r']) => {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        }
+        Value::Text(text) => text.clone(),
+        Value::List(_) => csv_cell(&Value::Text(json_value(value))),
+    }
+}
+
+// The header is taken from the keys of the first record
+pub fn to_csv(records: &[Record]) -> String {
+    let Some(first) = records.first() else {
+        return String::new();
+    };
+    let header = first
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .collect::<Vec<_>>();
+    std::iter::once(header.join(","))
+        .chain(records.iter().map(|record| {
+            record
+                .iter()
+                .map(|(_, value)| csv_cell(value))
+                .collect::<Vec<_>>()
+                .join(",")
+        }))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn run_record(
+    (year, day): (u16, u8),
+    part: Option<u8>,
+    status: &str,
+    answer: Option<String>,
+    (parse_time, parse_alloc): (Option<Duration>, Option<AllocStats>),
+    (solve_time, solve_alloc): (Option<Duration>, Option<AllocStats>),
+    error: Option<&SolveError>,
+) -> Record {
+    let [parse_allocations, parse_bytes, parse_peak] = alloc_values(parse_alloc);
+    let [solve_allocations, solve_bytes, solve_peak] = alloc_values(solve_alloc);
+    vec![
+        ("year", year.into()),
+        ("day", day.into()),
+        ("part", part.into()),
+        ("status", status.into()),
+        ("answer", answer.into()),
+        ("parse_ns", parse_time.into()),
+        ("solve_ns", solve_time.into()),
+        ("parse_allocations", parse_allocations),
+        ("parse_allocated_bytes", parse_bytes),
+        ("parse_peak_bytes", parse_peak),
+        ("solve_allocations", solve_allocations),
+        ("solve_allocated_bytes", solve_bytes),
+        ("solve_peak_bytes", solve_peak),
+        ("error", error.map(|e| e.to_string()).into()),
+    ]
+}
+
+// Null without the `alloc-stats` feature
+fn alloc_values(alloc: Option<AllocStats>) -> [Value; 3] {
+    [
+        alloc.map(|alloc| alloc.allocations).into(),
+        alloc.map(|alloc| alloc.bytes).into(),
+        alloc.map(|alloc| alloc.peak).into(),
+    ]
+}
+
+// One record per part, or a single one without a part when the day did not get to solving
+pub fn run_records(run: &DayRun) -> Vec<Record> {
+    match &run.outcome {
+        DayOutcome::MissingInput => vec![run_record(
+            (run.year, run.day),
+            None,
+            "missing_input",
+            None,
+            (None, None),
+            (None, None),
+            None,
+        )],
+        DayOutcome::Executed(execution) => execution_records(run.year, run.day, execution),
+    }
+}
+
+pub fn execution_records(year: u16, day: u8, execution: &Execution) -> Vec<Record> {
+    let parse = (Some(execution.parse_time), execution.parse_alloc);
+
+    match &execution.parts {
+        Err(e) => vec![run_record(
+            (year, day),
+            None,
+            if e.timed_out {
+                "timed_out"
+            } else {
+                "parse_error"
+            },
+            None,
+            parse,
+            (None, None),
+            Some(e),
+        )],
+        Ok(parts) => parts
+            .iter()
+            .map(|part| {
+                let (status, answer, error) = match &part.answer {
+                    Ok(answer) => ("ok", Some(answer.to_string()), None),
+                    Err(e) if e.timed_out => ("timed_out", None, Some(e)),
+                    Err(e) => ("error", None, Some(e)),
+                };
+                let part_number = Some(part.puzzle_number);
+                run_record(
+                    (year, day),
+                    part_number,
+                    status,
+                    answer,
+                    parse,
+                    (Some(part.time), part.alloc),
+                    error,
+                )
+            })
+            .collect(),
+    }
+}
+
+fn bench_record(
+    run: &BenchRun,
+    part: Option<u8>,
+    step: Option<&str>,
+    stats: Result<&Stats, Option<&SolveError>>,
+) -> Record {
+    let status = match (&run.outcome, stats, part) {
+        (BenchOutcome::MissingInput, _, _) => "missing_input",
+        (_, Ok(_), _) => "ok",
+        (_, Err(_), None) => "parse_error",
+        (_, Err(_), Some(_)) => "error",
+    };
+    let ok = stats.ok();
+    let [allocations, bytes, peak] = alloc_values(ok.and_then(|s| s.alloc));
+    vec![
+        ("year", run.year.into()),
+        ("day", run.day.into()),
+        ("part", part.into()),
+        ("step", step.into()),
+        ("status", status.into()),
+        ("runs", ok.map(|s| s.runs).into()),
+        ("min_ns", ok.map(|s| s.min).into()),
+        ("median_ns", ok.map(|s| s.median).into()),
+        ("mean_ns", ok.map(|s| s.mean).into()),
+        ("stddev_ns", ok.map(|s| s.stddev).into()),
+        ("allocations", allocations),
+        ("allocated_bytes", bytes),
+        ("peak_bytes", peak),
+        ("error", stats.err().flatten().map(|e| e.to_string()).into()),
+    ]
+}
+
+// One record for the parsing and one per part
+pub fn bench_records(run: &BenchRun) -> Vec<Record> {
+    match &run.outcome {
+        BenchOutcome::MissingInput => vec![bench_record(run, None, None, Err(None))],
+        BenchOutcome::Measured(Err(e)) => {
+            vec![bench_record(run, None, Some("parse"), Err(Some(e)))]
+        }
+        BenchOutcome::Measured(Ok(bench)) => {
+            std::iter::once(bench_record(run, None, Some("parse"), Ok(&bench.parse)))
+                .chain(bench.parts.iter().map(|part| {
+                    let stats = part.stats.as_ref().map_err(Some);
+                    bench_record(run, Some(part.puzzle_number), Some("solve"), stats)
+                }))
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use avc::utils::{Answer, PartResult};
+
+    use super::*;
+
+    fn executed(parts: Vec<Result<Answer, SolveError>>) -> DayRun {
+        let parts = parts
+            .into_iter()
+            .enumerate()
+            .map(|(i, answer)| PartResult {
+                puzzle_number: i as u8 + 1,
+                answer,
+                time: Duration::from_micros(3),
+                alloc: None,
+            })
+            .collect();
+        DayRun {
+            year: 2022,
+            day: 10,
+            outcome: DayOutcome::Executed(Execution {
+                parse_time: Duration::from_micros(1),
+                parse_alloc: None,
+                parts: Ok(parts),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_to_json() {
+        let mut run = executed(vec![
+            Ok(Answer::Grid(vec![vec![true, false], vec![false, true]])),
+            Err("no \"luck\"".into()),
+        ]);
+        if let DayOutcome::Executed(Execution {
+            parts: Ok(parts), ..
+        }) = &mut run.outcome
+        {
+            parts[0].alloc = Some(AllocStats {
+                allocations: 1,
+                bytes: 4096,
+                peak: 2048,
+            });
+        }
+        let records = run_records(&run);
+
+        assert_eq!(
+            to_json(&records),
+            r##"[
+  {"year": 2022, "day": 10, "part": 1, "status": "ok", "answer": "#.\n.#", "parse_ns": 1000, "solve_ns": 3000, "parse_allocations": null, "parse_allocated_bytes": null, "parse_peak_bytes": null, "solve_allocations": 1, "solve_allocated_bytes": 4096, "solve_peak_bytes": 2048, "error": null},
+  {"year": 2022, "day": 10, "part": 2, "status": "error", "answer": null, "parse_ns": 1000, "solve_ns": 3000, "parse_allocations": null, "parse_allocated_bytes": null, "parse_peak_bytes": null, "solve_allocations": null, "solve_allocated_bytes": null, "solve_peak_bytes": null, "error": "no \"luck\""}
+]"##
+        );
+        assert_eq!(to_json(&[]), "[]");
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let mut records = run_records(&executed(vec![Ok("a,b".into())]));
+        records.extend(run_records(&DayRun {
+            year: 2022,
+            day: 11,
+            outcome: DayOutcome::MissingInput,
+        }));
+
+        assert_eq!(
+            to_csv(&records),
+            "year,day,part,status,answer,parse_ns,solve_ns,parse_allocations,parse_allocated_bytes,parse_peak_bytes,solve_allocations,solve_allocated_bytes,solve_peak_bytes,error
+2022,10,1,ok,\"a,b\",1000,3000,,,,,,,
+2022,11,,missing_input,,,,,,,,,,"
+        );
+    }
+
+    #[test]
+    fn test_bench_records() {
+        let run = BenchRun {
+            year: 2021,
+            day: 1,
+            outcome: BenchOutcome::Measured(Err(SolveError::new("bad").at_line(2, "x"))),
+        };
+        assert_eq!(
+            to_csv(&bench_records(&run)),
+            "year,day,part,step,status,runs,min_ns,median_ns,mean_ns,stddev_ns,allocations,allocated_bytes,peak_bytes,error
+2021,1,,parse,parse_error,,,,,,,,,\"line 2: bad\n    | x\""
+        );
+    }
+}
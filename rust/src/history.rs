@@ -0,0 +1,371 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use avc::utils::{PuzzleContext, DEFAULT_VARIANT};
+
+use crate::{
+    bench::{BenchOutcome, BenchRun},
+    summary::{format_duration, format_table},
+};
+
+const HEADER: &str = "commit,dirty,timestamp,year,day,variant,params,step,runs,median_ns,min_ns";
+
+// The commit the binary was built from, empty outside of a git checkout, and whether the tree had
+// uncommitted changes then. Set by build.rs, so that a binary run away from its checkout or built
+// before a checkout keeps the commit of its code
+const BUILD_COMMIT: &str = env!("AVC_COMMIT");
+pub const BUILD_DIRTY: bool = matches!(env!("AVC_DIRTY").as_bytes(), b"true");
+
+pub fn build_commit() -> Option<&'static str> {
+    (!BUILD_COMMIT.is_empty()).then_some(BUILD_COMMIT)
+}
+
+// One measured step of a bench, as written in the history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub commit: String,
+    // Measured on a build with uncommitted changes, not the code of `commit`
+    pub dirty: bool,
+    pub timestamp: u64,
+    pub year: u16,
+    pub day: u8,
+    pub variant: String,
+    // The `--param` values, `name=value` separated by `;`, empty for the real input
+    pub params: String,
+    pub step: String,
+    pub runs: usize,
+    pub median: Duration,
+    pub min: Duration,
+}
+
+impl Entry {
+    fn key(&self) -> (u16, u8, &str, &str) {
+        (self.year, self.day, &self.variant, &self.step)
+    }
+
+    // Only the measures of a committed tree on the real input are comparable between commits
+    fn is_reference(&self) -> bool {
+        !self.dirty && self.params.is_empty()
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            self.commit,
+            self.dirty,
+            self.timestamp,
+            self.year,
+            self.day,
+            self.variant,
+            self.params,
+            self.step,
+            self.runs,
+            self.median.as_nanos(),
+            self.min.as_nanos()
+        )
+    }
+
+    fn parse(line: &str) -> Option<Entry> {
+        let fields = line.split(',').collect::<Vec<_>>();
+        let (commit, dirty, timestamp, year, day, variant, params, step, runs, median, min) =
+            match fields[..] {
+                [commit, dirty, timestamp, year, day, variant, params, step, runs, median, min] => {
+                    let dirty = dirty.parse().ok()?;
+                    (
+                        commit, dirty, timestamp, year, day, variant, params, step, runs, median,
+                        min,
+                    )
+                }
+                // The lines written before the dirty flag and the params were recorded
+                [commit, timestamp, year, day, variant, step, runs, median, min] => (
+                    commit, false, timestamp, year, day, variant, "", step, runs, median, min,
+                ),
+                _ => return None,
+            };
+        Some(Entry {
+            commit: commit.to_string(),
+            dirty,
+            timestamp: timestamp.parse().ok()?,
+            year: year.parse().ok()?,
+            day: day.parse().ok()?,
+            variant: variant.to_string(),
+            params: params.to_string(),
+            step: step.to_string(),
+            runs: runs.parse().ok()?,
+            median: Duration::from_nanos(median.parse().ok()?),
+            min: Duration::from_nanos(min.parse().ok()?),
+        })
+    }
+}
+
+// Every bench appends its measures to `.bench/history.csv`, next to the input cache
+pub struct History {
+    pub path: PathBuf,
+}
+
+impl History {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            path: root.join(".bench").join("history.csv"),
+        }
+    }
+
+    pub fn load(&self) -> io::Result<Vec<Entry>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+        text.lines()
+            .skip(1)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                Entry::parse(line).ok_or(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid history line {}", line),
+                ))
+            })
+            .collect()
+    }
+
+    pub fn append(&self, entries: &[Entry]) -> io::Result<()> {
+        fs::create_dir_all(self.path.parent().unwrap())?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        if file.metadata()?.len() == 0 {
+            writeln!(file, "{}", HEADER)?;
+        }
+        for entry in entries {
+            writeln!(file, "{}", entry.to_line())?;
+        }
+        Ok(())
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs())
+}
+
+// The steps measured without an error, the commit is `unknown` outside of a git checkout
+pub fn entries(
+    runs: &[BenchRun],
+    commit: Option<&str>,
+    dirty: bool,
+    context: &PuzzleContext,
+    timestamp: u64,
+) -> Vec<Entry> {
+    let variant = context.variant().unwrap_or(DEFAULT_VARIANT);
+    let params = context
+        .params()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join(";");
+    let mut entries = vec![];
+    for run in runs {
+        let BenchOutcome::Measured(Ok(bench)) = &run.outcome else {
+            continue;
+        };
+        let steps = std::iter::once(("parse".to_string(), &bench.parse)).chain(
+            bench.parts.iter().filter_map(|part| {
+                let stats = part.stats.as_ref().ok()?;
+                Some((format!("part {}", part.puzzle_number), stats))
+            }),
+        );
+        for (step, stats) in steps {
+            entries.push(Entry {
+                commit: commit.unwrap_or("unknown").to_string(),
+                dirty,
+                timestamp,
+                year: run.year,
+                day: run.day,
+                variant: variant.to_string(),
+                params: params.clone(),
+                step,
+                runs: stats.runs,
+                median: stats.median,
+                min: stats.min,
+            });
+        }
+    }
+    entries
+}
+
+// The last measure of each step on the commit starting with `commit`, among the benches of its
+// committed tree on the real input
+pub fn baseline<'a>(entries: &'a [Entry], commit: &str) -> Result<Vec<&'a Entry>, String> {
+    let matching = entries
+        .iter()
+        .filter(|entry| entry.commit.starts_with(commit) && entry.is_reference())
+        .collect::<Vec<_>>();
+    let Some(first) = matching.first() else {
+        return Err(format!(
+            "no bench of a clean build without --param recorded for commit {}",
+            commit
+        ));
+    };
+    if matching.iter().any(|entry| entry.commit != first.commit) {
+        return Err(format!("commit {} is ambiguous in the history", commit));
+    }
+
+    let mut last = BTreeMap::new();
+    for entry in matching {
+        last.insert(entry.key(), entry);
+    }
+    Ok(last.into_values().collect())
+}
+
+pub struct Change<'a> {
+    pub baseline: &'a Entry,
+    pub current: &'a Entry,
+}
+
+impl Change<'_> {
+    // Above 1 when the step got slower
+    pub fn ratio(&self) -> f64 {
+        self.current.median.as_secs_f64() / self.baseline.median.as_secs_f64().max(1e-12)
+    }
+
+    pub fn is_regression(&self, threshold_percent: u32) -> bool {
+        self.ratio() > 1.0 + threshold_percent as f64 / 100.0
+    }
+}
+
+// Pairs the current measures with the baseline ones of the same step
+pub fn compare<'a>(baseline: &[&'a Entry], current: &'a [Entry]) -> Vec<Change<'a>> {
+    current
+        .iter()
+        .filter_map(|current| {
+            let baseline = baseline.iter().find(|entry| entry.key() == current.key())?;
+            Some(Change { baseline, current })
+        })
+        .collect()
+}
+
+pub fn render_changes(changes: &[Change], threshold_percent: u32) -> String {
+    let header = ["Year", "Day", "Step", "Baseline", "Current", "Change", ""]
+        .map(String::from)
+        .to_vec();
+    let rows = changes
+        .iter()
+        .map(|change| {
+            vec![
+                format!("{:04}", change.current.year),
+                format!("{:02}", change.current.day),
+                change.current.step.clone(),
+                format_duration(change.baseline.median),
+                format_duration(change.current.median),
+                format!("{:+.1}%", (change.ratio() - 1.0) * 100.0),
+                match change.is_regression(threshold_percent) {
+                    true => "slower".to_string(),
+                    false => String::new(),
+                },
+            ]
+        })
+        .collect();
+    format_table(header, rows, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("avc-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn entry(commit: &str, timestamp: u64, step: &str, median_micros: u64) -> Entry {
+        Entry {
+            commit: commit.to_string(),
+            dirty: false,
+            timestamp,
+            year: 2022,
+            day: 16,
+            variant: "default".to_string(),
+            params: String::new(),
+            step: step.to_string(),
+            runs: 10,
+            median: Duration::from_micros(median_micros),
+            min: Duration::from_micros(median_micros / 2),
+        }
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let history = History::new(&temp_root("history"));
+        assert_eq!(history.load().unwrap(), vec![]);
+
+        let mut entries = vec![
+            entry("abc1", 1, "parse", 10),
+            entry("abc1", 1, "part 1", 20),
+        ];
+        entries[1].dirty = true;
+        entries[1].params = "row=10;max_coord=20".to_string();
+        history.append(&entries[..1]).unwrap();
+        history.append(&entries[1..]).unwrap();
+        assert_eq!(history.load().unwrap(), entries);
+        assert!(fs::read_to_string(&history.path)
+            .unwrap()
+            .starts_with(HEADER));
+
+        assert_eq!(
+            Entry::parse("abc1,1,2022,16,default,parse,10,10000,5000"),
+            Some(entry("abc1", 1, "parse", 10))
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut dirty = entry("abc1", 3, "part 1", 900);
+        dirty.dirty = true;
+        let mut with_params = entry("abc1", 4, "part 1", 5);
+        with_params.params = "row=10".to_string();
+        let history = vec![
+            entry("abc1", 1, "part 1", 100),
+            entry("abc1", 2, "part 1", 200),
+            entry("abc1", 2, "part 2", 100),
+            dirty,
+            with_params,
+            entry("abd2", 3, "part 1", 100),
+        ];
+        let baseline = baseline(&history, "abc").unwrap();
+        assert_eq!(baseline.len(), 2);
+        assert_eq!(baseline[0].median, Duration::from_micros(200));
+        assert_eq!(
+            super::baseline(&history, "ab").unwrap_err(),
+            "commit ab is ambiguous in the history"
+        );
+        assert!(super::baseline(&history, "fff").is_err());
+        assert!(super::baseline(&history[3..5], "abc").is_err());
+
+        let current = vec![
+            entry("abd2", 3, "part 1", 210),
+            entry("abd2", 3, "part 2", 150),
+        ];
+        let changes = compare(&baseline, &current);
+        assert!(!changes[0].is_regression(10));
+        assert!(changes[1].is_regression(10));
+        assert!(!changes[1].is_regression(50));
+
+        assert_eq!(
+            render_changes(&changes, 10),
+            "Year | Day | Step   | Baseline  | Current   | Change |
+-----+-----+--------+-----------+-----------+--------+-------
+2022 | 16  | part 1 | 200.00 µs | 210.00 µs | +5.0%  |
+2022 | 16  | part 2 | 100.00 µs | 150.00 µs | +50.0% | slower"
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use std::{
+    env, fs,
+    io::{self, Read, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+// Sent with every download, so that the site can tell where the requests come from
+const USER_AGENT: &str = "avc (advent of code solutions, input cache)";
+
+// The closest directory holding a `.session` or an `.inputs/`, from `start` upwards. Defaults to
+// `start`, where the cache is then created
+pub fn find_root(start: &Path) -> PathBuf {
+    start
+        .ancestors()
+        .find(|dir| dir.join(".session").is_file() || dir.join(".inputs").is_dir())
+        .unwrap_or(start)
+        .to_path_buf()
+}
+
+// Mirrors the layout used by run.sh: `.session` and `.inputs/` live at the root of the repository
+pub struct InputStore {
+    pub root: PathBuf,
+    pub base_url: String,
+}
+
+impl InputStore {
+    pub fn new(root: PathBuf, base_url: &str) -> Self {
+        Self {
+            root,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    // The root is found from the current directory, both locations can be overridden with the
+    // AVC_ROOT and AVC_BASE_URL environment variables
+    pub fn from_env() -> Self {
+        let root = env::var_os("AVC_ROOT")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| find_root(&env::current_dir().unwrap_or_default()));
+        let base_url = env::var("AVC_BASE_URL").unwrap_or(DEFAULT_BASE_URL.to_string());
+
+        Self::new(root, &base_url)
+    }
+
+    pub fn cache_path(&self, year: u16, day: u8) -> PathBuf {
+        self.root
+            .join(".inputs")
+            .join(format!("{}_{}.txt", year, day))
+    }
+
+    pub fn input_url(&self, year: u16, day: u8) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, year, day)
+    }
+
+    fn session(&self) -> io::Result<String> {
+        if let Ok(session) = env::var("AVC_SESSION") {
+            return Ok(session);
+        }
+        let path = self.root.join(".session");
+        let session = fs::read_to_string(&path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("cannot read session cookie from {}: {}", path.display(), e),
+            )
+        })?;
+        Ok(session.trim().to_string())
+    }
+
+    pub fn load_cached(&self, year: u16, day: u8) -> io::Result<Option<String>> {
+        match fs::read_to_string(self.cache_path(year, day)) {
+            Ok(input) => Ok(Some(input)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn fetch(&self, year: u16, day: u8) -> io::Result<String> {
+        let session = self.session()?;
+        let input = download(&self.input_url(year, day), &session)?;
+
+        // Write to a temporary file first so that an interrupted download never leaves a truncated input
+        let path = self.cache_path(year, day);
+        let dir = path.parent().unwrap();
+        fs::create_dir_all(dir)?;
+        let tmp_path = dir.join(format!(".{}_{}.txt.tmp", year, day));
+        fs::File::create(&tmp_path)?.write_all(input.as_bytes())?;
+        fs::rename(&tmp_path, &path)?;
+
+        Ok(input)
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> io::Result<String> {
+        match self.load_cached(year, day)? {
+            Some(input) => Ok(input),
+            None => self.fetch(year, day),
+        }
+    }
+}
+
+// Only the plain http of a local mirror, set with AVC_BASE_URL, is downloaded without curl. The
+// site itself is https, so fetching its inputs still requires curl
+fn download(url: &str, session: &str) -> io::Result<String> {
+    match url.strip_prefix("http://") {
+        Some(address) => download_plain(address, session),
+        None => download_with_curl(url, session),
+    }
+}
+
+// The standard library has no TLS support and the crate does not depend on a TLS library, so https
+// downloads need the curl executable, like run.sh did. Without it the inputs can still be saved in
+// the cache by hand
+fn download_with_curl(url: &str, session: &str) -> io::Result<String> {
+    let output = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--user-agent",
+            USER_AGENT,
+            "-H",
+        ])
+        .arg(format!("Cookie: {}", session))
+        .arg(url)
+        .output()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => io::Error::new(
+                e.kind(),
+                format!(
+                    "curl is needed to download {}, install it or save the input in the cache",
+                    url
+                ),
+            ),
+            _ => e,
+        })?;
+
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "failed to download {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    String::from_utf8(output.stdout).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn download_plain(address: &str, session: &str) -> io::Result<String> {
+    let (host, path) = match address.split_once('/') {
+        Some((host, path)) => (host, format!("/{}", path)),
+        None => (address, "/".to_string()),
+    };
+    let host_with_port = match host.contains(':') {
+        true => host.to_string(),
+        false => format!("{}:80", host),
+    };
+
+    let mut stream = TcpStream::connect(host_with_port)?;
+    // HTTP/1.0 keeps the response free of chunked encoding
+    write!(
+        stream,
+        "GET {} HTTP/1.0\r\nHost: {}\r\nCookie: {}\r\nUser-Agent: {}\r\n\r\n",
+        path, host, session, USER_AGENT
+    )?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed HTTP response"))?;
+    let status = head
+        .lines()
+        .next()
+        .and_then(|line| line.split(' ').nth(1))
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed HTTP status"))?;
+
+    if status != 200 {
+        return Err(io::Error::other(format!(
+            "failed to download http://{}: status {}",
+            address, status
+        )));
+    }
+
+    Ok(body.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpListener, process, thread};
+
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("avc-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join(".session"), "session=1234\n").unwrap();
+        root
+    }
+
+    // Serves a single request and returns the raw request text
+    fn stub_server(response: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..n]);
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_fetch_writes_cache() {
+        let root = temp_root("fetch");
+        let (url, server) = stub_server("HTTP/1.0 200 OK\r\n\r\n1\n2\n3\n");
+        let store = InputStore::new(root.clone(), &url);
+
+        assert_eq!(store.fetch(2022, 1).unwrap(), "1\n2\n3\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.0\r\n"));
+        assert!(request.contains("Cookie: session=1234\r\n"));
+        assert!(request.contains(&format!("User-Agent: {}\r\n", USER_AGENT)));
+        assert_eq!(
+            fs::read_to_string(root.join(".inputs/2022_1.txt")).unwrap(),
+            "1\n2\n3\n"
+        );
+        assert_eq!(store.load_cached(2022, 1).unwrap().unwrap(), "1\n2\n3\n");
+    }
+
+    #[test]
+    fn test_fetch_error_keeps_cache_empty() {
+        let root = temp_root("fetch-error");
+        let (url, server) = stub_server("HTTP/1.0 404 Not Found\r\n\r\nnot found");
+        let store = InputStore::new(root, &url);
+
+        assert!(store.fetch(2022, 2).is_err());
+        server.join().unwrap();
+        assert!(store.load_cached(2022, 2).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_find_root() {
+        let root = temp_root("find-root");
+        let nested = root.join("rust/src");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_root(&nested), root);
+
+        fs::remove_file(root.join(".session")).unwrap();
+        assert_eq!(find_root(&nested), nested);
+        fs::create_dir_all(root.join(".inputs")).unwrap();
+        assert_eq!(find_root(&nested), root);
+    }
+
+    #[test]
+    fn test_get_uses_cache() {
+        let root = temp_root("get");
+        fs::create_dir_all(root.join(".inputs")).unwrap();
+        fs::write(root.join(".inputs/2021_5.txt"), "cached").unwrap();
+        // Nothing listens on this address, any download attempt would fail
+        let store = InputStore::new(root, "http://127.0.0.1:1");
+
+        assert_eq!(store.get(2021, 5).unwrap(), "cached");
+    }
+}
@@ -0,0 +1,10 @@
+//! Solutions to the Advent of Code puzzles.
+//!
+//! Each day implements [`utils::Solution`] and is registered in [`days::DAYS`] by the build
+//! script. The [`utils`] module holds what the solutions share: parsing helpers, geometry,
+//! puzzle parameters, logging and cancellation, along with what runs them. The tooling to fetch,
+//! run, check and benchmark them is the `avc` binary.
+
+/// The registry of the solved days
+pub mod days;
+pub mod utils;
@@ -1,63 +1,251 @@
-use core::panic;
+// Like `println!`, but a reader that stops early, like `head`, does not make it panic
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        $crate::print_line(format_args!($($arg)*))
+    };
+}
+
+mod answers;
+mod batch;
+mod bench;
+mod cli;
+mod commands;
+mod compare;
+mod export;
+mod history;
+mod inputs;
+#[cfg(test)]
+mod real_inputs;
+mod report;
+mod scaffold;
+mod server;
+mod summary;
+mod testing;
+
 use std::{
-    env,
-    io::{self, Read},
-    time::Instant,
+    env, fmt,
+    io::{self, Write},
+    process::{self, ExitCode},
 };
 
-use days::get_days;
-use utils::Day;
-
-mod days;
-mod utils;
+use avc::{
+    days::{get_day, DAYS},
+    utils::PuzzleContext,
+};
 
-fn execute(year: u16, day: &Day, puzzle_number: u8, input: &String) {
-    let start = Instant::now();
-    let puzzle = match puzzle_number {
-        1 => day.puzzle_1,
-        2 => day.puzzle_2,
-        _ => panic!(),
-    };
+use crate::{
+    answers::AnswerStore,
+    cli::{Command, InputSource},
+    // `compare` and `report` are called through the module, their names are taken by modules
+    commands::{
+        list, load_input, open_log, run, run_bench, run_examples, run_inputs, run_summary,
+        selected_days, serve, test, verify, SUMMARY_TIMEOUT,
+    },
+    inputs::InputStore,
+};
 
-    let result = puzzle(input);
+// Status of a process killed by SIGPIPE, which is what the reader of a closed pipe expects
+const BROKEN_PIPE_STATUS: i32 = 141;
 
-    println!(
-        "Year {:04} \t Day {:02} \t Time: {:.2e} s \t Puzzle {}: {}",
-        year,
-        day.day,
-        start.elapsed().as_secs_f32(),
-        puzzle_number,
-        result
-    );
+// The reader of the output went away: the rest of it is dropped and the process stops quietly
+fn print_line(line: fmt::Arguments) {
+    if let Err(e) = writeln!(io::stdout().lock(), "{}", line) {
+        match e.kind() {
+            io::ErrorKind::BrokenPipe => process::exit(BROKEN_PIPE_STATUS),
+            _ => panic!("failed printing to stdout: {}", e),
+        }
+    }
 }
 
-fn get_input() -> io::Result<String> {
-    let mut res = String::new();
-    io::stdin().lock().read_to_string(&mut res)?;
-    Ok(res)
+// Hands each command to its implementation in `commands`
+fn dispatch(command: Command) -> Result<(), String> {
+    match command {
+        Command::Help(command) => {
+            outln!("{}", cli::help(command.as_deref()));
+            Ok(())
+        }
+        Command::Report {
+            year,
+            output,
+            timeout,
+        } => {
+            let days = match year {
+                Some(year) => selected_days(year, None)?,
+                None => DAYS.iter().collect(),
+            };
+            commands::report(&days, &output, timeout.unwrap_or(SUMMARY_TIMEOUT))
+        }
+        Command::Serve { port, timeout } => serve(port, timeout.unwrap_or(SUMMARY_TIMEOUT)),
+        Command::Fetch { year, day } => {
+            let store = InputStore::from_env();
+            store
+                .fetch(year, day)
+                .map_err(|e| format!("failed to fetch input: {}", e))?;
+            outln!("{}", store.cache_path(year, day).display());
+            Ok(())
+        }
+        Command::New { year, day } => {
+            // The files go to the checkout the command is run from, not the one the binary was
+            // built from
+            let root = scaffold::find_repository(&env::current_dir().unwrap_or_default())
+                .ok_or("cannot find the repository, run avc new from within it")?;
+            let created = scaffold::create_day(
+                &root.join("rust").join("src").join("days"),
+                &root.join("examples"),
+                year,
+                day,
+            )
+            .map_err(|e| format!("cannot create day: {}", e))?;
+            for path in created {
+                outln!("{}", path.display());
+            }
+            Ok(())
+        }
+        Command::List { year } => list(year),
+        Command::Test {
+            year,
+            day,
+            part,
+            timeout,
+        } => {
+            let days = match year {
+                Some(year) => selected_days(year, day)?,
+                None => DAYS.iter().collect(),
+            };
+            test(&days, part, timeout.unwrap_or(SUMMARY_TIMEOUT))
+        }
+        Command::Bench {
+            year,
+            day,
+            format,
+            params,
+            log,
+            baseline,
+            timeout,
+        } => {
+            let context = params.with_log(open_log(&log)?);
+            run_bench(
+                &selected_days(year, day)?,
+                format,
+                &context,
+                baseline.as_ref(),
+                timeout.unwrap_or(SUMMARY_TIMEOUT),
+            )
+        }
+        Command::Compare {
+            year,
+            day,
+            part,
+            input,
+            params,
+            log,
+            timeout,
+        } => {
+            let context = params.with_log(open_log(&log)?);
+            let input = load_input(&InputStore::from_env(), year, day, &input)?;
+            commands::compare(
+                selected_days(year, Some(day))?[0],
+                part,
+                &input,
+                &context,
+                timeout.unwrap_or(SUMMARY_TIMEOUT),
+            )
+        }
+        Command::Verify { timeout } => verify(timeout.unwrap_or(SUMMARY_TIMEOUT)),
+        Command::Run {
+            year: None,
+            day: None,
+            format,
+            log,
+            timeout,
+            serial,
+            ..
+        } => {
+            let context = PuzzleContext::default().with_log(open_log(&log)?);
+            run_summary(
+                &DAYS.iter().collect::<Vec<_>>(),
+                format,
+                &context,
+                timeout.unwrap_or(SUMMARY_TIMEOUT),
+                serial,
+            )
+        }
+        Command::Run {
+            year: Some(year),
+            day: None,
+            format,
+            log,
+            timeout,
+            serial,
+            ..
+        } => {
+            let context = PuzzleContext::default().with_log(open_log(&log)?);
+            run_summary(
+                &selected_days(year, None)?,
+                format,
+                &context,
+                timeout.unwrap_or(SUMMARY_TIMEOUT),
+                serial,
+            )
+        }
+        Command::Run {
+            year: Some(year),
+            day: Some(day),
+            part,
+            input,
+            format,
+            record,
+            params,
+            log,
+            timeout,
+            serial: _,
+        } => {
+            let context = params.with_log(open_log(&log)?);
+            if input == InputSource::Example {
+                return run_examples(year, day, part, format, &context, timeout);
+            }
+            let selected_day =
+                get_day(year, day).ok_or(format!("cannot find day {} for year {}", day, year))?;
+            if let InputSource::Directory(dir) = &input {
+                return run_inputs(selected_day, dir, part, format, &context, timeout);
+            }
+            let store = InputStore::from_env();
+            let input = load_input(&store, year, day, &input)?;
+            let answers = AnswerStore::new(&store.root);
+            run(
+                selected_day,
+                part,
+                &input,
+                format,
+                &context,
+                timeout,
+                record.then_some(&answers),
+            )
+        }
+        Command::Run {
+            year: None,
+            day: Some(_),
+            ..
+        } => unreachable!("a day is always given after a year"),
+    }
 }
 
-fn main() {
+fn main() -> ExitCode {
     let args: Vec<String> = env::args().collect();
-    let input = get_input().expect("Failed to parse input");
-    let year = args
-        .get(1)
-        .and_then(|d| str::parse::<u16>(d).ok())
-        .expect("Expected a year number");
-    let days = get_days(year);
-    let day_number = args
-        .get(2)
-        .and_then(|d| str::parse::<usize>(d).ok())
-        .expect("Expected a day number");
-    let selected_day = days
-        .get(day_number - 1)
-        .expect("Cannot find selected day for selected year");
-    let puzzle_number = args.get(3).and_then(|p| str::parse::<usize>(p).ok());
-
-    if puzzle_number.is_none() || puzzle_number == Some(1) {
-        execute(year, selected_day, 1, &input);
-    }
-    if puzzle_number.is_none() || puzzle_number == Some(2) {
-        execute(year, selected_day, 2, &input);
+
+    let command = match cli::parse_args(&args[1..]) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    match dispatch(command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
     }
 }
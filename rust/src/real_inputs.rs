@@ -0,0 +1,99 @@
+// Checks every day against its real input and recorded answers, found in the `.inputs/` and
+// `.answers/` directories of AVC_ROOT (found from the current directory by default). The days
+// without both are skipped, so that the suite passes on a fresh checkout. Each part gets
+// AVC_TEST_TIMEOUT seconds, 60 by default.
+
+use std::{env, time::Duration};
+
+use avc::{
+    days::DAYS,
+    utils::{Day, PuzzleContext},
+};
+use rayon::prelude::*;
+
+use crate::{
+    answers::{self, AnswerStore, Verdict},
+    inputs::InputStore,
+};
+
+// The variants too slow for the real input, checked by the examples only
+const SKIPPED_VARIANTS: &[(u16, u8, &str, u8)] = &[
+    // Brute force, the bitmask variant finds the same answer in well under a second
+    (2022, 16, "default", 2),
+];
+
+fn timeout() -> Duration {
+    env::var("AVC_TEST_TIMEOUT")
+        .ok()
+        .map(|seconds| {
+            seconds
+                .parse::<f64>()
+                .expect("AVC_TEST_TIMEOUT must be a number of seconds")
+        })
+        .map_or(Duration::from_secs(60), Duration::from_secs_f64)
+}
+
+// The failures of a day, `None` when its input or answers are missing
+fn check_day(
+    day: &Day,
+    inputs: &InputStore,
+    answers: &AnswerStore,
+    timeout: Duration,
+) -> Option<Vec<String>> {
+    let input = inputs.load_cached(day.year, day.day).unwrap()?;
+    let recorded = [1, 2]
+        .into_iter()
+        .filter_map(|n| Some((n, answers.load(day.year, day.day, n).unwrap()?)))
+        .collect::<Vec<_>>();
+    if recorded.is_empty() {
+        return None;
+    }
+
+    // Every variant of a part must find its recorded answer
+    let mut failures = vec![];
+    for (variant, puzzle_number) in (day.variants)() {
+        let Some(expected) = recorded.iter().find(|(n, _)| *n == puzzle_number) else {
+            continue;
+        };
+        if SKIPPED_VARIANTS.contains(&(day.year, day.day, variant, puzzle_number)) {
+            continue;
+        }
+        let context = PuzzleContext::default().with_variant(variant);
+        let execution = (day.execute_with_timeout)(&input, &[puzzle_number], &context, timeout);
+        for (n, verdict) in answers::check(&execution, std::slice::from_ref(expected)) {
+            if !matches!(verdict, Verdict::Match) {
+                failures.push(format!(
+                    "{} day {} part {} variant {}: {}",
+                    day.year, day.day, n, variant, verdict
+                ));
+            }
+        }
+    }
+    Some(failures)
+}
+
+#[test]
+fn test_recorded_answers() {
+    let inputs = InputStore::from_env();
+    let answers = AnswerStore::new(&inputs.root);
+    let timeout = timeout();
+
+    // The days only wait for their worker thread, like in `run_days` they get their own pool so
+    // that the solutions using rayon are not starved by them
+    let checked = rayon::ThreadPoolBuilder::new()
+        .build()
+        .unwrap()
+        .install(|| {
+            DAYS.par_iter()
+                .filter_map(|day| check_day(day, &inputs, &answers, timeout))
+                .collect::<Vec<_>>()
+        });
+    eprintln!(
+        "{} days checked against their real input, {} skipped",
+        checked.len(),
+        DAYS.len() - checked.len()
+    );
+
+    let failures = checked.concat();
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
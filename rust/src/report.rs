@@ -0,0 +1,353 @@
+use std::time::Duration;
+
+use avc::utils::{memory::AllocStats, Answer, PartResult, DEFAULT_VARIANT};
+
+use crate::{
+    answers::Verdict,
+    history::Entry,
+    summary::{format_duration, total_time, DayOutcome, DayRun},
+};
+
+// The latest benches drawn in the sparklines
+const HISTORY_POINTS: usize = 30;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { padding: 0.3em 0.8em; border-bottom: 1px solid #ddd; text-align: left; vertical-align: top; }
+td.time { text-align: right; font-variant-numeric: tabular-nums; }
+pre { margin: 0; font-size: 0.7em; line-height: 1; }
+.ok { color: #1a7f37; }
+.wrong, .error { color: #cf222e; }
+.unchecked, .missing { color: #888; }
+svg polyline { fill: none; stroke: #0969da; stroke-width: 1.5; }
+";
+
+pub struct DayReport {
+    pub run: DayRun,
+    // Empty when no answer is recorded
+    pub verdicts: Vec<(u8, Verdict)>,
+}
+
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// The total median time of the default variant of a day on its real input in each bench of the
+// history, oldest first. The steps of a bench share its commit and timestamp
+pub fn day_history(history: &[Entry], year: u16, day: u8) -> Vec<Duration> {
+    let mut totals: Vec<(&str, u64, Duration)> = vec![];
+    for entry in history
+        .iter()
+        .filter(|entry| entry.year == year && entry.day == day)
+        .filter(|entry| entry.variant == DEFAULT_VARIANT && entry.params.is_empty())
+    {
+        match totals.last_mut() {
+            Some((commit, timestamp, total))
+                if *commit == entry.commit && *timestamp == entry.timestamp =>
+            {
+                *total += entry.median
+            }
+            _ => totals.push((&entry.commit, entry.timestamp, entry.median)),
+        }
+    }
+    totals.into_iter().map(|(_, _, total)| total).collect()
+}
+
+// An inline SVG of the times, scaled from zero to the slowest one
+pub fn sparkline(times: &[Duration]) -> String {
+    let (width, height) = (120.0, 24.0);
+    let times = &times[times.len().saturating_sub(HISTORY_POINTS)..];
+    let Some(last) = times.last() else {
+        return String::new();
+    };
+    let max = times
+        .iter()
+        .map(Duration::as_secs_f64)
+        .fold(f64::MIN_POSITIVE, f64::max);
+    // A single bench is drawn as a flat line
+    let step = width / (times.len().max(2) - 1) as f64;
+    let mut points = times
+        .iter()
+        .enumerate()
+        .map(|(i, time)| {
+            let y = height - 1.0 - time.as_secs_f64() / max * (height - 2.0);
+            format!("{:.1},{:.1}", i as f64 * step, y)
+        })
+        .collect::<Vec<_>>();
+    if times.len() == 1 {
+        points.push(points[0].replacen("0.0,", &format!("{:.1},", width), 1));
+    }
+
+    format!(
+        "<svg width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\"><title>{n} benches, last {last}</title><polyline points=\"{points}\"/></svg>",
+        w = width,
+        h = height,
+        n = times.len(),
+        last = format_duration(*last),
+        points = points.join(" "),
+    )
+}
+
+fn answer_html(part: &PartResult, verdict: Option<&Verdict>) -> String {
+    let (class, status) = match (&part.answer, verdict) {
+        (Err(e), _) if e.timed_out => ("error", "timed out".to_string()),
+        (Err(e), _) => ("error", escape(&e.to_string())),
+        (Ok(_), Some(Verdict::Match)) => ("ok", "ok".to_string()),
+        // Expected grids do not fit next to the answer
+        (Ok(_), Some(Verdict::Mismatch { expected, .. })) if expected.contains('\n') => {
+            ("wrong", "wrong".to_string())
+        }
+        (Ok(_), Some(Verdict::Mismatch { expected, .. })) => {
+            ("wrong", format!("wrong, expected {}", escape(expected)))
+        }
+        (Ok(_), Some(Verdict::Failed(_))) => ("error", "error".to_string()),
+        (Ok(_), None) => ("unchecked", "unchecked".to_string()),
+    };
+    let answer = match &part.answer {
+        // Grids are only readable as they are drawn
+        Ok(answer @ Answer::Grid(_)) => format!("<pre>{}</pre>", escape(&answer.to_string())),
+        Ok(answer) => format!("<code>{}</code>", escape(&answer.to_string())),
+        Err(_) => String::new(),
+    };
+    format!("{} <span class=\"{}\">{}</span>", answer, class, status)
+}
+
+// The allocations of the whole day, with the highest peak of its steps
+fn memory_cell(execution_alloc: impl Iterator<Item = Option<AllocStats>>) -> String {
+    let allocs = execution_alloc.flatten().collect::<Vec<_>>();
+    if allocs.is_empty() {
+        return String::new();
+    }
+    let total = AllocStats {
+        allocations: allocs.iter().map(|alloc| alloc.allocations).sum(),
+        bytes: allocs.iter().map(|alloc| alloc.bytes).sum(),
+        peak: allocs.iter().map(|alloc| alloc.peak).max().unwrap_or(0),
+    };
+    total.to_string()
+}
+
+fn day_row(report: &DayReport, history: &[Entry]) -> String {
+    let run = &report.run;
+    let mut cells = vec![format!("<td>{:02}</td>", run.day)];
+    match &run.outcome {
+        DayOutcome::MissingInput => {
+            cells.push("<td colspan=\"6\" class=\"missing\">missing input</td>".to_string());
+            cells.push("<td></td>".to_string());
+        }
+        DayOutcome::Executed(execution) => {
+            let parts = execution.parts.as_deref().unwrap_or_default();
+            for puzzle_number in [1, 2] {
+                let part = parts
+                    .iter()
+                    .find(|part| part.puzzle_number == puzzle_number);
+                let verdict = report
+                    .verdicts
+                    .iter()
+                    .find(|(n, _)| *n == puzzle_number)
+                    .map(|(_, verdict)| verdict);
+                cells.push(match (part, &execution.parts) {
+                    (Some(part), _) => format!("<td>{}</td>", answer_html(part, verdict)),
+                    (None, Err(e)) if e.timed_out => {
+                        "<td class=\"error\">timed out</td>".to_string()
+                    }
+                    (None, Err(e)) => format!(
+                        "<td class=\"error\">cannot parse input: {}</td>",
+                        escape(&e.to_string())
+                    ),
+                    (None, Ok(_)) => "<td></td>".to_string(),
+                });
+            }
+
+            let time = |part: Option<&PartResult>| {
+                part.map(|part| format_duration(part.time))
+                    .unwrap_or_default()
+            };
+            cells.push(format!(
+                "<td class=\"time\">{}</td>",
+                format_duration(execution.parse_time)
+            ));
+            for puzzle_number in [1, 2] {
+                let part = parts
+                    .iter()
+                    .find(|part| part.puzzle_number == puzzle_number);
+                cells.push(format!("<td class=\"time\">{}</td>", time(part)));
+            }
+            cells.push(format!(
+                "<td class=\"time\">{}</td>",
+                format_duration(total_time(execution))
+            ));
+            let allocs =
+                std::iter::once(execution.parse_alloc).chain(parts.iter().map(|part| part.alloc));
+            cells.push(format!("<td>{}</td>", memory_cell(allocs)));
+        }
+    }
+    cells.push(format!(
+        "<td>{}</td>",
+        sparkline(&day_history(history, run.year, run.day))
+    ));
+    format!("<tr>{}</tr>", cells.join(""))
+}
+
+// A self-contained page with a table per year: the answers checked against the recorded ones,
+// the timings and allocations of this run, and the bench history of each day
+pub fn render_html(reports: &[DayReport], history: &[Entry], commit: Option<&str>) -> String {
+    let executed = reports
+        .iter()
+        .filter(|report| matches!(report.run.outcome, DayOutcome::Executed(_)))
+        .count();
+    let checked = reports.iter().flat_map(|report| &report.verdicts);
+    let matching = checked
+        .clone()
+        .filter(|(_, verdict)| matches!(verdict, Verdict::Match))
+        .count();
+
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code report</title>\n<style>{}</style>\n</head>\n<body>\n<h1>Advent of Code report</h1>\n<p>Commit <code>{}</code>, {}/{} days run, {}/{} recorded answers match</p>\n",
+        STYLE,
+        escape(commit.unwrap_or("unknown")),
+        executed,
+        reports.len(),
+        matching,
+        checked.count(),
+    );
+
+    let mut years = reports
+        .iter()
+        .map(|report| report.run.year)
+        .collect::<Vec<_>>();
+    years.dedup();
+    for year in years {
+        html.push_str(&format!(
+            "<h2>{}</h2>\n<table>\n<tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Parse</th><th>Part 1</th><th>Part 2</th><th>Total</th><th>Memory</th><th>History</th></tr>\n",
+            year
+        ));
+        for report in reports.iter().filter(|report| report.run.year == year) {
+            html.push_str(&day_row(report, history));
+            html.push('\n');
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use avc::utils::{Execution, SolveError};
+
+    use super::*;
+
+    fn entry(commit: &str, timestamp: u64, day: u8, median_micros: u64) -> Entry {
+        Entry {
+            commit: commit.to_string(),
+            dirty: false,
+            timestamp,
+            year: 2022,
+            day,
+            variant: DEFAULT_VARIANT.to_string(),
+            params: String::new(),
+            step: "part 1".to_string(),
+            runs: 10,
+            median: Duration::from_micros(median_micros),
+            min: Duration::from_micros(median_micros),
+        }
+    }
+
+    #[test]
+    fn test_day_history() {
+        let mut bitmask = entry("abc", 1, 16, 1000);
+        bitmask.variant = "bitmask".to_string();
+        let mut with_params = entry("abc", 1, 16, 1000);
+        with_params.params = "minutes=10".to_string();
+        let history = vec![
+            entry("abc", 1, 16, 10),
+            entry("abc", 1, 16, 20),
+            bitmask,
+            with_params,
+            entry("abc", 1, 10, 5),
+            entry("abc", 2, 16, 40),
+            entry("def", 2, 16, 50),
+        ];
+        assert_eq!(
+            day_history(&history, 2022, 16),
+            [30, 40, 50].map(Duration::from_micros)
+        );
+        assert_eq!(day_history(&history, 2021, 16), []);
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        let svg = sparkline(&[10, 20].map(Duration::from_micros));
+        assert!(svg.contains("<title>2 benches, last 20.00 µs</title>"));
+        assert!(svg.contains("points=\"0.0,12.0 120.0,1.0\""));
+        assert!(sparkline(&[Duration::from_micros(10)]).contains("points=\"0.0,1.0 120.0,1.0\""));
+    }
+
+    #[test]
+    fn test_render_html() {
+        let part = |puzzle_number, answer: Result<Answer, SolveError>| PartResult {
+            puzzle_number,
+            answer,
+            time: Duration::from_millis(1),
+            alloc: None,
+        };
+        let reports = vec![
+            DayReport {
+                run: DayRun {
+                    year: 2022,
+                    day: 1,
+                    outcome: DayOutcome::Executed(Execution {
+                        parse_time: Duration::from_millis(2),
+                        parse_alloc: None,
+                        parts: Ok(vec![part(1, Ok(24000.into())), part(2, Ok("<a&b>".into()))]),
+                    }),
+                },
+                verdicts: vec![
+                    (1, Verdict::Match),
+                    (
+                        2,
+                        Verdict::Mismatch {
+                            expected: "c".to_string(),
+                            actual: "<a&b>".to_string(),
+                        },
+                    ),
+                ],
+            },
+            DayReport {
+                run: DayRun {
+                    year: 2022,
+                    day: 2,
+                    outcome: DayOutcome::MissingInput,
+                },
+                verdicts: vec![],
+            },
+        ];
+        let history = vec![entry("abc", 1, 1, 10), entry("abc", 2, 1, 20)];
+
+        let html = render_html(&reports, &history, Some("abc"));
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("Commit <code>abc</code>, 1/2 days run, 1/2 recorded answers match"));
+        assert_eq!(html.matches("<h2>").count(), 1);
+        assert!(html.contains("<code>24000</code> <span class=\"ok\">ok</span>"));
+        assert!(html.contains(
+            "<code>&lt;a&amp;b&gt;</code> <span class=\"wrong\">wrong, expected c</span>"
+        ));
+        assert!(html.contains("<td class=\"time\">4.00 ms</td>"));
+        assert!(html.contains("missing input"));
+        assert!(html.contains("<title>2 benches, last 20.00 µs</title>"));
+        // Nothing is loaded from elsewhere
+        assert!(!html.contains("http"));
+    }
+}
@@ -0,0 +1,172 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+const TEMPLATE: &str = include_str!("days/day_00.rs");
+
+// The closest directory holding `rust/src/days`, from `start` upwards, like the input cache is
+// found. `None` outside of a checkout of the repository
+pub fn find_repository(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join("rust").join("src").join("days").is_dir())
+        .map(Path::to_path_buf)
+}
+
+pub fn day_path(days_dir: &Path, year: u16, day: u8) -> PathBuf {
+    days_dir
+        .join(format!("y{}", year))
+        .join(format!("day_{:02}.rs", day))
+}
+
+pub fn example_dir(examples_dir: &Path, year: u16, day: u8) -> PathBuf {
+    examples_dir
+        .join(format!("y{}", year))
+        .join(format!("day_{:02}", day))
+        .join("example")
+}
+
+// Creates a file that does not exist yet, with an error naming it otherwise
+fn create_new(path: &Path, content: &str) -> io::Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::AlreadyExists => {
+                io::Error::new(e.kind(), format!("{} already exists", path.display()))
+            }
+            _ => e,
+        })?;
+    file.write_all(content.as_bytes())
+}
+
+// build.rs registers every file it finds, creating the files is all it takes to add a day. The
+// tests of the day check the answers of an `example` whose input and answers are left empty, to be
+// filled in from the statement. Returns the created files
+pub fn create_day(
+    days_dir: &Path,
+    examples_dir: &Path,
+    year: u16,
+    day: u8,
+) -> io::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("day {} is not between 1 and 25", day),
+        ));
+    }
+
+    let path = day_path(days_dir, year, day);
+    fs::create_dir_all(path.parent().unwrap())?;
+
+    let content = TEMPLATE
+        .lines()
+        .filter(|line| !line.starts_with("// Template used by"))
+        .map(|line| {
+            line.replace("YEAR", &year.to_string())
+                .replace("DAY", &day.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    // Refuses to overwrite a day that already exists
+    create_new(&path, &(content + "\n"))?;
+    let mut created = vec![path];
+
+    // The example may have been written before the solution, it is kept as is
+    let example_dir = example_dir(examples_dir, year, day);
+    fs::create_dir_all(&example_dir)?;
+    for name in ["input.txt", "part_1.txt", "part_2.txt"] {
+        let path = example_dir.join(name);
+        match create_new(&path, "") {
+            Ok(()) => created.push(path),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    // The days and examples directories of a fresh temporary root
+    fn temp_dirs(name: &str) -> (PathBuf, PathBuf) {
+        let dir = env::temp_dir().join(format!("avc-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        (dir.join("days"), dir.join("examples"))
+    }
+
+    #[test]
+    fn test_create_day() {
+        let (days_dir, examples_dir) = temp_dirs("new");
+        let created = create_day(&days_dir, &examples_dir, 2024, 3).unwrap();
+
+        let example = examples_dir.join("y2024/day_03/example");
+        assert_eq!(
+            created,
+            vec![
+                days_dir.join("y2024/day_03.rs"),
+                example.join("input.txt"),
+                example.join("part_1.txt"),
+                example.join("part_2.txt"),
+            ]
+        );
+        let content = fs::read_to_string(&created[0]).unwrap();
+        assert!(content.starts_with("// https://adventofcode.com/2024/day/3\n"));
+        assert!(content.contains("impl Solution for Puzzle"));
+        assert!(content.contains("assert_example::<Puzzle>(2024, 3, \"example\", 2);"));
+        assert_eq!(fs::read_to_string(example.join("input.txt")).unwrap(), "");
+    }
+
+    #[test]
+    fn test_create_day_keeps_existing_files() {
+        let (days_dir, examples_dir) = temp_dirs("new-existing");
+        fs::create_dir_all(days_dir.join("y2024")).unwrap();
+        fs::write(days_dir.join("y2024/day_01.rs"), "work in progress").unwrap();
+
+        let error = create_day(&days_dir, &examples_dir, 2024, 1).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(
+            fs::read_to_string(days_dir.join("y2024/day_01.rs")).unwrap(),
+            "work in progress"
+        );
+
+        let example = example_dir(&examples_dir, 2024, 2);
+        fs::create_dir_all(&example).unwrap();
+        fs::write(example.join("input.txt"), "1\n2\n").unwrap();
+        let created = create_day(&days_dir, &examples_dir, 2024, 2).unwrap();
+        assert!(!created.contains(&example.join("input.txt")));
+        assert_eq!(
+            fs::read_to_string(example.join("input.txt")).unwrap(),
+            "1\n2\n"
+        );
+    }
+
+    #[test]
+    fn test_find_repository() {
+        let root = env::temp_dir().join(format!("avc-repository-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("rust/src/days/y2022")).unwrap();
+        fs::create_dir_all(root.join("examples")).unwrap();
+
+        for start in ["", "rust", "rust/src/days/y2022", "examples"] {
+            assert_eq!(find_repository(&root.join(start)), Some(root.clone()));
+        }
+        assert_eq!(find_repository(&env::temp_dir()), None);
+    }
+
+    #[test]
+    fn test_create_day_out_of_range() {
+        let (days_dir, examples_dir) = temp_dirs("new-range");
+        assert!(create_day(&days_dir, &examples_dir, 2024, 26).is_err());
+        assert!(!days_dir.join("y2024").exists());
+        assert!(!examples_dir.exists());
+    }
+}
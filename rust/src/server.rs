@@ -0,0 +1,409 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    time::{Duration, Instant},
+};
+
+use avc::{
+    days::{get_day, DAYS},
+    utils::{alive_workers, parse_param, PuzzleContext},
+};
+
+use crate::{
+    export::{self, json_string, Record, Value},
+    summary::format_duration,
+};
+
+// Inputs are a few kilobytes, this only keeps a wrong request from filling the memory
+const MAX_BODY: usize = 16 << 20;
+// A client that stops sending does not hold a thread of the pool for longer
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+// Parts that ignore their cancel token keep their worker thread after the timeout, past this many
+// workers on top of the pool the new requests are refused until some of them stop
+const MAX_LEFT_BEHIND: usize = 16;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    // The query string split on `&`, with the keys and values percent-decoded
+    pub query: Vec<(String, String)>,
+    pub body: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    // Always JSON
+    pub body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: format!("{{\"error\": {}}}", json_string(message)),
+        }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+// Decodes the `%XX` escapes and the `+` standing for a space in a query key or value
+fn percent_decode(text: &str) -> Result<String, Response> {
+    let invalid = || Response::error(400, &format!("invalid percent-encoding in {}", text));
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = rest.get(..2).ok_or_else(invalid)?;
+                let hex = std::str::from_utf8(hex).map_err(|_| invalid())?;
+                bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+                rest = &rest[2..];
+            }
+            byte => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+// Reads the request line, the headers and a body of `Content-Length` bytes
+pub fn read_request(reader: &mut impl BufRead) -> Result<Request, Response> {
+    let bad_request = |e: io::Error| Response::error(400, &e.to_string());
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(bad_request)?;
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(target), Some(_)) = (words.next(), words.next(), words.next()) else {
+        return Err(Response::error(400, "malformed request line"));
+    };
+
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).map_err(bad_request)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(Response::error(400, "malformed header"));
+        };
+        if name.eq_ignore_ascii_case("content-length") {
+            length = value
+                .trim()
+                .parse()
+                .map_err(|_| Response::error(400, "invalid Content-Length"))?;
+        }
+    }
+    if length > MAX_BODY {
+        return Err(Response::error(413, "the body is too large"));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(bad_request)?;
+    let body =
+        String::from_utf8(body).map_err(|_| Response::error(400, "the body is not UTF-8"))?;
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            Ok((percent_decode(key)?, percent_decode(value)?))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        query,
+        body,
+    })
+}
+
+fn list_days() -> Response {
+    let records = DAYS
+        .iter()
+        .map(|day| {
+            let params = day
+                .params
+                .iter()
+                .map(|&(name, value)| vec![("name", name.into()), ("default", value.into())])
+                .collect();
+            let variants = (day.variants)()
+                .into_iter()
+                .map(|(name, puzzle_number)| {
+                    vec![("name", name.into()), ("part", puzzle_number.into())]
+                })
+                .collect();
+            let record: Record = vec![
+                ("year", day.year.into()),
+                ("day", day.day.into()),
+                ("params", Value::List(params)),
+                ("variants", Value::List(variants)),
+            ];
+            record
+        })
+        .collect::<Vec<_>>();
+    Response::ok(export::to_json(&records))
+}
+
+// Solves the body with the part, variant and parameters of the query, the answers are the
+// records of `avc run --format json`
+fn solve(year: &str, day: &str, request: &Request, timeout: Duration) -> Response {
+    let selected = year
+        .parse()
+        .ok()
+        .zip(day.parse().ok())
+        .and_then(|(year, day)| get_day(year, day));
+    let Some(selected) = selected else {
+        return Response::error(404, &format!("cannot find day {} for year {}", day, year));
+    };
+
+    let mut puzzle_numbers: &[u8] = &[1, 2];
+    let mut context = PuzzleContext::default();
+    for (key, value) in &request.query {
+        match (key.as_str(), value.as_str()) {
+            ("part", "1") => puzzle_numbers = &[1],
+            ("part", "2") => puzzle_numbers = &[2],
+            ("part", part) => {
+                return Response::error(400, &format!("unknown puzzle number {}", part))
+            }
+            ("variant", name) => context = context.with_variant(name),
+            ("param", param) => match parse_param(param) {
+                Ok((name, value)) => context = context.with_param(name, value),
+                Err(e) => return Response::error(400, &e.to_string()),
+            },
+            (key, _) => return Response::error(400, &format!("unknown query parameter {}", key)),
+        }
+    }
+    if let Err(e) = context
+        .check_names(selected.params)
+        .and_then(|_| context.check_variant(&(selected.variants)()))
+    {
+        return Response::error(400, &e.to_string());
+    }
+    // Called on the pool of `serve`, whose threads each wait for at most one worker
+    if alive_workers() >= rayon::current_num_threads() + MAX_LEFT_BEHIND {
+        return Response::error(503, "too many parts did not stop after their timeout");
+    }
+
+    let execution =
+        (selected.execute_with_timeout)(&request.body, puzzle_numbers, &context, timeout);
+    let records = export::execution_records(selected.year, selected.day, &execution);
+    Response::ok(export::to_json(&records))
+}
+
+// `GET /days` lists the registered days, `POST /days/<year>/<day>` solves the input in the body
+pub fn handle(request: &Request, timeout: Duration) -> Response {
+    let segments = request
+        .path
+        .trim_matches('/')
+        .split('/')
+        .collect::<Vec<_>>();
+    match (request.method.as_str(), &segments[..]) {
+        ("GET", ["days"]) => list_days(),
+        ("POST", ["days", year, day]) => solve(year, day, request, timeout),
+        (_, ["days"]) | (_, ["days", _, _]) => Response::error(405, "method not allowed"),
+        _ => Response::error(404, &format!("no route for {}", request.path)),
+    }
+}
+
+pub fn write_response(writer: &mut impl Write, response: &Response) -> io::Result<()> {
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        response.body.len(),
+        response.body
+    )?;
+    writer.flush()
+}
+
+// One request per connection, logged on stderr with its status and duration
+fn handle_connection(mut stream: TcpStream, timeout: Duration) -> io::Result<()> {
+    let start = Instant::now();
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let (target, response) = match read_request(&mut BufReader::new(&stream)) {
+        Ok(request) => (
+            format!("{} {}", request.method, request.path),
+            handle(&request, timeout),
+        ),
+        Err(response) => ("-".to_string(), response),
+    };
+    eprintln!(
+        "{} {} {}",
+        target,
+        response.status,
+        format_duration(start.elapsed())
+    );
+    write_response(&mut stream, &response)
+}
+
+// Handles the connections on a thread pool until the listener fails. Each part is given up after
+// `timeout`, so that a slow request only holds its thread for that long
+pub fn serve(listener: TcpListener, timeout: Duration) -> io::Result<()> {
+    // The requests only wait for their worker thread, like in `run_summary` they get their own
+    // pool so that the solutions using rayon are not starved by them
+    let pool = rayon::ThreadPoolBuilder::new()
+        .build()
+        .map_err(io::Error::other)?;
+    for stream in listener.incoming() {
+        let stream = stream?;
+        pool.spawn(move || {
+            if let Err(e) = handle_connection(stream, timeout) {
+                eprintln!("error: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, thread};
+
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(60);
+
+    fn request(method: &str, target: &str, body: &str) -> Request {
+        let text = format!(
+            "{} {} HTTP/1.1\r\nHost: localhost\r\ncontent-length: {}\r\n\r\n{}",
+            method,
+            target,
+            body.len(),
+            body
+        );
+        read_request(&mut text.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_read_request() {
+        let parsed = request(
+            "POST",
+            "/days/2022/15?part=1&param=row%3D10&variant=bit+mask%21",
+            "x\ny\n",
+        );
+        assert_eq!(
+            parsed,
+            Request {
+                method: "POST".to_string(),
+                path: "/days/2022/15".to_string(),
+                query: vec![
+                    ("part".to_string(), "1".to_string()),
+                    ("param".to_string(), "row=10".to_string()),
+                    ("variant".to_string(), "bit mask!".to_string()),
+                ],
+                body: "x\ny\n".to_string(),
+            }
+        );
+
+        assert_eq!(
+            read_request(&mut "hello\r\n\r\n".as_bytes()).unwrap_err(),
+            Response::error(400, "malformed request line")
+        );
+        for target in ["/?part=%2", "/?part=%zz", "/?part=%ff"] {
+            let text = format!("GET {} HTTP/1.1\r\n\r\n", target);
+            assert_eq!(
+                read_request(&mut text.as_bytes()).unwrap_err().status,
+                400,
+                "{}",
+                target
+            );
+        }
+        let too_large = format!(
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY + 1
+        );
+        assert_eq!(
+            read_request(&mut too_large.as_bytes()).unwrap_err().status,
+            413
+        );
+    }
+
+    #[test]
+    fn test_handle() {
+        let days = handle(&request("GET", "/days", ""), TIMEOUT);
+        assert_eq!(days.status, 200);
+        assert!(days.body.contains(
+            "{\"year\": 2022, \"day\": 16, \"params\": [], \"variants\": [{\"name\": \"default\", \"part\": 1}, {\"name\": \"default\", \"part\": 2}, {\"name\": \"bitmask\", \"part\": 1}, {\"name\": \"bitmask\", \"part\": 2}]}"
+        ));
+        assert!(days.body.contains(
+            "{\"year\": 2022, \"day\": 15, \"params\": [{\"name\": \"row\", \"default\": \"2000000\"}"
+        ));
+
+        let example = avc::utils::fixtures::get_examples(2022, 10)[0].input;
+        let solved = handle(&request("POST", "/days/2022/10?part=1", example), TIMEOUT);
+        assert_eq!(solved.status, 200);
+        assert!(solved.body.starts_with(
+            "[\n  {\"year\": 2022, \"day\": 10, \"part\": 1, \"status\": \"ok\", \"answer\": \"13140\""
+        ));
+        assert_eq!(solved.body.matches("\"part\"").count(), 1);
+
+        for (method, target, status, error) in [
+            (
+                "POST",
+                "/days/2021/26",
+                404,
+                "cannot find day 26 for year 2021",
+            ),
+            (
+                "POST",
+                "/days/2021/1?part=3",
+                400,
+                "unknown puzzle number 3",
+            ),
+            (
+                "POST",
+                "/days/2021/1?variant=fast",
+                400,
+                "unknown variant fast, expected one of default",
+            ),
+            (
+                "POST",
+                "/days/2021/1?param=row=10",
+                400,
+                "unknown parameter row",
+            ),
+            ("GET", "/days/2021/1", 405, "method not allowed"),
+            ("GET", "/", 404, "no route for /"),
+        ] {
+            let response = handle(&request(method, target, ""), TIMEOUT);
+            assert_eq!(response.status, status, "{} {}", method, target);
+            assert!(response.body.contains(error), "{}", response.body);
+        }
+    }
+
+    #[test]
+    fn test_serve() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, TIMEOUT));
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "GET /days HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n"));
+        assert!(response.contains("\"year\": 2022, \"day\": 16"));
+    }
+}
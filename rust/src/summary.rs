@@ -0,0 +1,342 @@
+use std::time::Duration;
+
+use avc::utils::{
+    memory::{format_bytes, AllocStats},
+    Answer, Execution,
+};
+
+pub enum DayOutcome {
+    // The input is not cached, the day was not executed
+    MissingInput,
+    Executed(Execution),
+}
+
+pub struct DayRun {
+    pub year: u16,
+    pub day: u8,
+    pub outcome: DayOutcome,
+}
+
+impl DayRun {
+    pub fn has_failures(&self) -> bool {
+        match &self.outcome {
+            DayOutcome::MissingInput => false,
+            DayOutcome::Executed(execution) => execution.has_failures(),
+        }
+    }
+
+    pub fn total_time(&self) -> Duration {
+        match &self.outcome {
+            DayOutcome::MissingInput => Duration::ZERO,
+            DayOutcome::Executed(execution) => total_time(execution),
+        }
+    }
+}
+
+pub fn total_time(execution: &Execution) -> Duration {
+    let parts_time = match &execution.parts {
+        Ok(parts) => parts.iter().map(|part| part.time).sum(),
+        Err(_) => Duration::ZERO,
+    };
+    execution.parse_time + parts_time
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64();
+    match seconds {
+        s if s >= 1.0 => format!("{:.2} s", s),
+        s if s >= 1e-3 => format!("{:.2} ms", s * 1e3),
+        s => format!("{:.2} µs", s * 1e6),
+    }
+}
+
+pub fn answer_cell(answer: &Answer) -> String {
+    match answer {
+        // Grids do not fit in a cell, they are printed below the table
+        Answer::Grid(rows) => format!(
+            "[grid {}x{}]",
+            rows.first().map(|row| row.len()).unwrap_or(0),
+            rows.len()
+        ),
+        answer => answer.to_string(),
+    }
+}
+
+fn has_alloc(run: &DayRun) -> bool {
+    match &run.outcome {
+        DayOutcome::MissingInput => false,
+        DayOutcome::Executed(execution) => execution_has_alloc(execution),
+    }
+}
+
+pub fn execution_has_alloc(execution: &Execution) -> bool {
+    execution.parse_alloc.is_some()
+        || execution
+            .parts
+            .iter()
+            .flatten()
+            .any(|part| part.alloc.is_some())
+}
+
+fn row_cells(run: &DayRun, with_alloc: bool) -> Vec<String> {
+    let mut cells = vec![format!("{:04}", run.year), format!("{:02}", run.day)];
+    match &run.outcome {
+        DayOutcome::MissingInput => {
+            cells.push("missing input".to_string());
+            cells.extend(vec![String::new(); step_headers(with_alloc).len() - 1]);
+        }
+        DayOutcome::Executed(execution) => cells.extend(execution_cells(execution, with_alloc)),
+    }
+    cells
+}
+
+// The columns of `execution_cells`
+pub fn step_headers(with_alloc: bool) -> Vec<&'static str> {
+    match with_alloc {
+        true => vec![
+            "Parse",
+            "Allocs",
+            "Allocated",
+            "Peak",
+            "Part 1",
+            "Time",
+            "Allocs",
+            "Allocated",
+            "Peak",
+            "Part 2",
+            "Time",
+            "Allocs",
+            "Allocated",
+            "Peak",
+        ],
+        false => vec!["Parse", "Part 1", "Time", "Part 2", "Time"],
+    }
+}
+
+// Each step gets the allocation count, the bytes allocated and the peak memory after its time
+// when the allocations were counted
+pub fn execution_cells(execution: &Execution, with_alloc: bool) -> Vec<String> {
+    let alloc_cells = |alloc: Option<AllocStats>| match alloc {
+        Some(alloc) => vec![
+            alloc.allocations.to_string(),
+            format_bytes(alloc.bytes),
+            format_bytes(alloc.peak),
+        ],
+        None => vec![String::new(); 3],
+    };
+    let step_width = if with_alloc { 5 } else { 2 };
+    let mut cells = vec![format_duration(execution.parse_time)];
+    if with_alloc {
+        cells.extend(alloc_cells(execution.parse_alloc));
+    }
+    match &execution.parts {
+        Err(e) => {
+            cells.push(match e.timed_out {
+                true => "timed out".to_string(),
+                false => "parse error".to_string(),
+            });
+            cells.extend(vec![String::new(); step_width * 2 - 1]);
+        }
+        Ok(parts) => {
+            for puzzle_number in [1, 2] {
+                match parts
+                    .iter()
+                    .find(|part| part.puzzle_number == puzzle_number)
+                {
+                    Some(part) => {
+                        cells.push(match &part.answer {
+                            Ok(answer) => answer_cell(answer),
+                            Err(e) if e.timed_out => "timed out".to_string(),
+                            Err(_) => "error".to_string(),
+                        });
+                        cells.push(format_duration(part.time));
+                        if with_alloc {
+                            cells.extend(alloc_cells(part.alloc));
+                        }
+                    }
+                    None => cells.extend(vec![String::new(); step_width]),
+                }
+            }
+        }
+    }
+    cells
+}
+
+pub fn render_table(runs: &[DayRun]) -> String {
+    let with_alloc = runs.iter().any(has_alloc);
+    let header = ["Year", "Day"]
+        .into_iter()
+        .chain(step_headers(with_alloc))
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    let executed = runs
+        .iter()
+        .filter(|run| matches!(run.outcome, DayOutcome::Executed(_)))
+        .count();
+    let total: Duration = runs.iter().map(DayRun::total_time).sum();
+    let mut footer = vec![String::new(); header.len()];
+    footer[0] = "Total".to_string();
+    footer[1] = format!("{}/{}", executed, runs.len());
+    footer[2] = format_duration(total);
+
+    let rows = runs
+        .iter()
+        .map(|run| row_cells(run, with_alloc))
+        .collect::<Vec<_>>();
+    format_table(header, rows, Some(footer))
+}
+
+// The characters shown on a terminal, without the escape sequences of the colors
+fn visible_width(cell: &str) -> usize {
+    let mut width = 0;
+    let mut chars = cell.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => {
+                chars.by_ref().find(|&c| c == 'm');
+            }
+            _ => width += 1,
+        }
+    }
+    width
+}
+
+// Aligns the cells in columns, with separators below the header and above the footer
+pub fn format_table(
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+    footer: Option<Vec<String>>,
+) -> String {
+    let has_footer = footer.is_some();
+    let rows = std::iter::once(header)
+        .chain(rows)
+        .chain(footer)
+        .collect::<Vec<_>>();
+
+    let widths = (0..rows[0].len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| visible_width(&row[i]))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    let separator = widths
+        .iter()
+        .map(|&w| "-".repeat(w))
+        .collect::<Vec<_>>()
+        .join("-+-");
+
+    let mut lines = vec![];
+    for (i, row) in rows.iter().enumerate() {
+        if i == 1 || (has_footer && i == rows.len() - 1) {
+            lines.push(separator.clone());
+        }
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{}{}", cell, " ".repeat(width - visible_width(cell))))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        lines.push(line.trim_end().to_string());
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use avc::utils::PartResult;
+
+    use super::*;
+
+    fn executed(year: u16, day: u8, answers: [Result<Answer, &str>; 2]) -> DayRun {
+        let parts = answers
+            .into_iter()
+            .enumerate()
+            .map(|(i, answer)| PartResult {
+                puzzle_number: i as u8 + 1,
+                answer: answer.map_err(|e| e.into()),
+                time: Duration::from_millis(2),
+                alloc: None,
+            })
+            .collect();
+        DayRun {
+            year,
+            day,
+            outcome: DayOutcome::Executed(Execution {
+                parse_time: Duration::from_millis(1),
+                parse_alloc: None,
+                parts: Ok(parts),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(12)), "12.00 µs");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.50 ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50 s");
+    }
+
+    #[test]
+    fn test_render_table() {
+        let runs = vec![
+            executed(2022, 1, [Ok(Answer::Integer(24000)), Ok("CMZ".into())]),
+            DayRun {
+                year: 2022,
+                day: 2,
+                outcome: DayOutcome::MissingInput,
+            },
+            executed(
+                2022,
+                3,
+                [Ok(Answer::Grid(vec![vec![true; 4]; 2])), Err("no")],
+            ),
+        ];
+
+        assert_eq!(
+            render_table(&runs),
+            "Year  | Day | Parse         | Part 1     | Time    | Part 2 | Time
+------+-----+---------------+------------+---------+--------+--------
+2022  | 01  | 1.00 ms       | 24000      | 2.00 ms | CMZ    | 2.00 ms
+2022  | 02  | missing input |            |         |        |
+2022  | 03  | 1.00 ms       | [grid 4x2] | 2.00 ms | error  | 2.00 ms
+------+-----+---------------+------------+---------+--------+--------
+Total | 2/3 | 10.00 ms      |            |         |        |"
+        );
+        assert!(runs[2].has_failures() && !runs[1].has_failures());
+    }
+
+    #[test]
+    fn test_render_table_with_alloc() {
+        let mut run = executed(2022, 14, [Ok(Answer::Integer(24)), Ok(Answer::Integer(93))]);
+        if let DayOutcome::Executed(execution) = &mut run.outcome {
+            execution.parse_alloc = Some(AllocStats {
+                allocations: 4,
+                bytes: 2_500,
+                peak: 1_200,
+            });
+        }
+        let runs = vec![
+            run,
+            DayRun {
+                year: 2022,
+                day: 15,
+                outcome: DayOutcome::MissingInput,
+            },
+        ];
+
+        assert_eq!(
+            render_table(&runs),
+            "Year  | Day | Parse         | Allocs | Allocated | Peak    | Part 1 | Time    | Allocs | Allocated | Peak | Part 2 | Time    | Allocs | Allocated | Peak
+------+-----+---------------+--------+-----------+---------+--------+---------+--------+-----------+------+--------+---------+--------+-----------+-----
+2022  | 14  | 1.00 ms       | 4      | 2.50 kB   | 1.20 kB | 24     | 2.00 ms |        |           |      | 93     | 2.00 ms |        |           |
+2022  | 15  | missing input |        |           |         |        |         |        |           |      |        |         |        |           |
+------+-----+---------------+--------+-----------+---------+--------+---------+--------+-----------+------+--------+---------+--------+-----------+-----
+Total | 1/2 | 5.00 ms       |        |           |         |        |         |        |           |      |        |         |        |           |"
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use std::time::Duration;
+
+use avc::utils::{fixtures::Example, Day};
+
+use crate::{
+    answers::{self, Verdict},
+    summary::{format_duration, format_table},
+};
+
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+pub struct PartCheck {
+    pub puzzle_number: u8,
+    pub verdict: Verdict,
+    pub time: Duration,
+}
+
+pub struct ExampleCheck {
+    pub year: u16,
+    pub day: u8,
+    // `None` for the days without examples
+    pub example: Option<&'static str>,
+    // Only the parts with an answer in the statement are checked
+    pub parts: Vec<PartCheck>,
+}
+
+impl ExampleCheck {
+    pub fn passed(&self) -> bool {
+        self.parts
+            .iter()
+            .all(|part| matches!(part.verdict, Verdict::Match))
+    }
+}
+
+// Solves the parts of `example` in `puzzle_numbers` that have an expected answer
+pub fn check_example(
+    day: &Day,
+    example: &'static Example,
+    puzzle_numbers: &[u8],
+    timeout: Duration,
+) -> ExampleCheck {
+    let expected = puzzle_numbers
+        .iter()
+        .filter_map(|&n| example.answer(n).map(|answer| (n, answer.to_string())))
+        .collect::<Vec<_>>();
+    let numbers = expected.iter().map(|(n, _)| *n).collect::<Vec<_>>();
+
+    let parts = match example.context() {
+        Ok(context) if !numbers.is_empty() => {
+            let execution = (day.execute_with_timeout)(example.input, &numbers, &context, timeout);
+            let times = match &execution.parts {
+                Ok(parts) => parts.iter().map(|part| part.time).collect(),
+                Err(_) => vec![Duration::ZERO; numbers.len()],
+            };
+            answers::check(&execution, &expected)
+                .into_iter()
+                .zip(times)
+                .map(|((puzzle_number, verdict), time)| PartCheck {
+                    puzzle_number,
+                    verdict,
+                    time,
+                })
+                .collect()
+        }
+        Ok(_) => vec![],
+        Err(e) => numbers
+            .iter()
+            .map(|&puzzle_number| PartCheck {
+                puzzle_number,
+                verdict: Verdict::Failed(e.clone()),
+                time: Duration::ZERO,
+            })
+            .collect(),
+    };
+
+    ExampleCheck {
+        year: day.year,
+        day: day.day,
+        example: Some(example.name),
+        parts,
+    }
+}
+
+fn part_cell(part: &PartCheck, color: bool) -> String {
+    let (text, passed) = match &part.verdict {
+        Verdict::Match => (format!("ok {}", format_duration(part.time)), true),
+        Verdict::Mismatch { .. } => (format!("wrong {}", format_duration(part.time)), false),
+        Verdict::Failed(e) if e.timed_out => ("timed out".to_string(), false),
+        Verdict::Failed(_) => ("error".to_string(), false),
+    };
+    match (color, passed) {
+        (false, _) => text,
+        (true, true) => format!("{}{}{}", GREEN, text, RESET),
+        (true, false) => format!("{}{}{}", RED, text, RESET),
+    }
+}
+
+// One row per example with a cell per part, `color` highlights the passed and failed cells
+pub fn render_matrix(checks: &[ExampleCheck], color: bool) -> String {
+    let header = ["Year", "Day", "Example", "Part 1", "Part 2"]
+        .map(String::from)
+        .to_vec();
+    let rows = checks
+        .iter()
+        .map(|check| {
+            let mut cells = vec![
+                format!("{:04}", check.year),
+                format!("{:02}", check.day),
+                check.example.unwrap_or("no example").to_string(),
+            ];
+            for puzzle_number in [1, 2] {
+                cells.push(
+                    check
+                        .parts
+                        .iter()
+                        .find(|part| part.puzzle_number == puzzle_number)
+                        .map(|part| part_cell(part, color))
+                        .unwrap_or_default(),
+                );
+            }
+            cells
+        })
+        .collect();
+
+    let examples = checks.iter().filter(|check| check.example.is_some());
+    let passed = examples.clone().filter(|check| check.passed()).count();
+    let mut footer = vec![String::new(); header.len()];
+    footer[0] = "Total".to_string();
+    footer[2] = format!("{}/{} passed", passed, examples.count());
+    format_table(header, rows, Some(footer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(puzzle_number: u8, verdict: Verdict) -> PartCheck {
+        PartCheck {
+            puzzle_number,
+            verdict,
+            time: Duration::from_micros(12),
+        }
+    }
+
+    #[test]
+    fn test_check_example() {
+        let day = avc::days::get_day(2022, 10).unwrap();
+        let example = avc::utils::fixtures::get_examples(2022, 10)[0];
+        let check = check_example(day, example, &[2], Duration::from_secs(60));
+
+        assert_eq!(check.example, Some("example"));
+        assert_eq!(check.parts.len(), 1);
+        assert_eq!(check.parts[0].puzzle_number, 2);
+        assert!(check.passed());
+    }
+
+    #[test]
+    fn test_render_matrix() {
+        let checks = vec![
+            ExampleCheck {
+                year: 2022,
+                day: 1,
+                example: Some("example"),
+                parts: vec![
+                    part(1, Verdict::Match),
+                    part(
+                        2,
+                        Verdict::Mismatch {
+                            expected: "45000".to_string(),
+                            actual: "24000".to_string(),
+                        },
+                    ),
+                ],
+            },
+            ExampleCheck {
+                year: 2022,
+                day: 2,
+                example: Some("small"),
+                parts: vec![part(1, Verdict::Failed("no".into()))],
+            },
+            ExampleCheck {
+                year: 2022,
+                day: 3,
+                example: None,
+                parts: vec![],
+            },
+        ];
+
+        assert_eq!(
+            render_matrix(&checks, false),
+            "Year  | Day | Example    | Part 1      | Part 2
+------+-----+------------+-------------+---------------
+2022  | 01  | example    | ok 12.00 µs | wrong 12.00 µs
+2022  | 02  | small      | error       |
+2022  | 03  | no example |             |
+------+-----+------------+-------------+---------------
+Total |     | 0/2 passed |             |"
+        );
+
+        let colored = render_matrix(&checks[..1], true);
+        assert!(colored.contains("\x1b[32mok 12.00 µs\x1b[0m | \x1b[31mwrong 12.00 µs\x1b[0m"));
+        assert!(colored.starts_with("Year  | Day | Example    | Part 1      | Part 2\n"));
+    }
+}
@@ -0,0 +1,230 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use super::{memory::AllocStats, solve, Execution, PuzzleContext, Solution, SolveError};
+
+/// How long each step of a day is measured
+pub struct BenchConfig {
+    /// Time spent running before measuring, to warm the caches up
+    pub warmup: Duration,
+    /// Time after which a step stops being repeated
+    pub budget: Duration,
+    /// Keeps the samples of the fastest steps from filling the memory
+    pub max_runs: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: Duration::from_millis(100),
+            budget: Duration::from_secs(1),
+            max_runs: 100_000,
+        }
+    }
+}
+
+/// The durations measured for a step
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    /// Number of samples
+    pub runs: usize,
+    /// Fastest sample
+    pub min: Duration,
+    /// Middle sample, the mean of both middle ones for an even count
+    pub median: Duration,
+    /// Mean of the samples
+    pub mean: Duration,
+    /// Population standard deviation of the samples
+    pub stddev: Duration,
+    /// What one run of the step allocated
+    pub alloc: Option<AllocStats>,
+}
+
+impl Stats {
+    /// The statistics of `samples` without allocations, `None` when there is no sample
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let runs = sorted.len();
+        let median = match runs % 2 {
+            0 => (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2,
+            _ => sorted[runs / 2],
+        };
+        let seconds = sorted.iter().map(Duration::as_secs_f64).collect::<Vec<_>>();
+        let mean = seconds.iter().sum::<f64>() / runs as f64;
+        let variance = seconds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / runs as f64;
+
+        Some(Stats {
+            runs,
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            alloc: None,
+        })
+    }
+}
+
+// Warms up then repeats `step` until the budget or the run count is spent, running it at least
+// once. The allocations were counted on the run already made of the step in `first`, the next ones
+// make the same. The step is not run again without a deadline when that run took longer than the
+// budget: its time is the only sample then
+fn measure<T>(
+    config: &BenchConfig,
+    first: Duration,
+    alloc: Option<AllocStats>,
+    mut step: impl FnMut() -> Result<T, SolveError>,
+) -> Result<Stats, SolveError> {
+    if first > config.budget {
+        return Ok(Stats {
+            alloc,
+            ..Stats::from_samples(&[first]).unwrap()
+        });
+    }
+
+    let start = Instant::now();
+    while start.elapsed() < config.warmup {
+        black_box(step()?);
+    }
+
+    let mut samples = vec![];
+    let start = Instant::now();
+    loop {
+        let sample_start = Instant::now();
+        black_box(step()?);
+        samples.push(sample_start.elapsed());
+        if start.elapsed() >= config.budget || samples.len() >= config.max_runs {
+            break;
+        }
+    }
+
+    Ok(Stats {
+        alloc,
+        ..Stats::from_samples(&samples).unwrap()
+    })
+}
+
+/// The measures of a part
+pub struct PartBench {
+    /// 1 or 2
+    pub puzzle_number: u8,
+    /// Fails when the part fails or timed out
+    pub stats: Result<Stats, SolveError>,
+}
+
+/// The measures of a day on an input
+pub struct DayBench {
+    /// The measures of the parsing
+    pub parse: Stats,
+    /// The measures of the parts, in the order they were asked for
+    pub parts: Vec<PartBench>,
+}
+
+/// Measures the parsing and the parts of `execution`, a run of the same input that tells which
+/// parts to measure and what they allocate. The parts that failed or timed out in it are not run
+/// again, the steps that took longer than the budget in it are not repeated. Fails as a whole
+/// when the input cannot be parsed
+pub fn bench<S: Solution>(
+    input: &str,
+    execution: &Execution,
+    config: &BenchConfig,
+    context: &PuzzleContext,
+) -> Result<DayBench, SolveError> {
+    let executed = execution.parts.as_ref().map_err(Clone::clone)?;
+    let parse = measure(config, execution.parse_time, execution.parse_alloc, || {
+        S::parse(black_box(input))
+    })?;
+    let parsed = S::parse(input)?;
+    let context = PuzzleContext::from_defaults(S::PARAMS).merge(context);
+
+    let parts = executed
+        .iter()
+        .map(|part| PartBench {
+            puzzle_number: part.puzzle_number,
+            stats: match &part.answer {
+                Ok(_) => measure(config, part.time, part.alloc, || {
+                    solve::<S>(black_box(&parsed), part.puzzle_number, &context)
+                }),
+                Err(e) => Err(e.clone()),
+            },
+        })
+        .collect();
+
+    Ok(DayBench { parse, parts })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(|&s| Duration::from_micros(s)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&micros(&[4, 2, 9, 5, 5, 4, 7, 4])).unwrap();
+        assert_eq!(stats.runs, 8);
+        assert_eq!(stats.min, Duration::from_micros(2));
+        assert_eq!(
+            stats.median,
+            Duration::from_micros(4) + Duration::from_nanos(500)
+        );
+        assert_eq!(stats.mean.as_nanos(), 5000);
+        assert_eq!(stats.stddev.as_nanos(), 2000);
+
+        let stats = Stats::from_samples(&micros(&[3, 1, 2])).unwrap();
+        assert_eq!(stats.median, Duration::from_micros(2));
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_measure_stops_on_error() {
+        let config = BenchConfig {
+            warmup: Duration::ZERO,
+            budget: Duration::ZERO,
+            max_runs: 10,
+        };
+        let mut calls = 0;
+        let stats = measure(&config, Duration::ZERO, None, || {
+            calls += 1;
+            Ok(calls)
+        })
+        .unwrap();
+        assert_eq!((stats.runs, calls), (1, 1));
+
+        let config = BenchConfig {
+            budget: Duration::from_secs(60),
+            ..config
+        };
+        let first = Duration::from_millis(1);
+        assert_eq!(measure(&config, first, None, || Ok(())).unwrap().runs, 10);
+
+        let result = measure(&config, first, None, || Err::<(), _>("no".into()));
+        assert_eq!(result.unwrap_err().to_string(), "no");
+    }
+
+    #[test]
+    fn test_measure_does_not_repeat_slow_steps() {
+        let config = BenchConfig {
+            warmup: Duration::from_secs(60),
+            budget: Duration::from_millis(10),
+            max_runs: 10,
+        };
+        let first = Duration::from_millis(50);
+        let mut calls = 0;
+        let stats = measure(&config, first, None, || {
+            calls += 1;
+            Ok(calls)
+        })
+        .unwrap();
+        assert_eq!((stats.runs, calls), (1, 0));
+        assert_eq!(stats.median, first);
+    }
+}
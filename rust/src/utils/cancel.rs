@@ -0,0 +1,58 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use super::SolveError;
+
+/// Set by the runner when a part exceeds its deadline, long searches poll it to stop early
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    /// Tells every clone of the token to stop
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether `cancel` was called on a clone of the token
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// To be called with `?` inside the loops of a search
+    pub fn check(&self) -> Result<(), SolveError> {
+        match self.is_cancelled() {
+            true => Err(SolveError::new("cancelled")),
+            false => Ok(()),
+        }
+    }
+}
+
+// Two tokens are the same when cancelling one cancels the other
+impl PartialEq for CancelToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.cancelled, &other.cancelled)
+    }
+}
+
+impl Eq for CancelToken {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel() {
+        let token = CancelToken::default();
+        let shared = token.clone();
+        assert_eq!(shared.check(), Ok(()));
+
+        token.cancel();
+        assert!(shared.is_cancelled());
+        assert_eq!(shared.check().unwrap_err().to_string(), "cancelled");
+        assert_ne!(token, CancelToken::default());
+    }
+}
@@ -0,0 +1,235 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use super::{parse_lines, CancelToken, Log, SolveError};
+
+/// Named values that differ between the examples and the real input, like the row to look at,
+/// the log the solutions report their progress to, the token telling them to stop and the
+/// implementation of the parts to use
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PuzzleContext {
+    params: BTreeMap<String, String>,
+    log: Log,
+    cancel: Option<CancelToken>,
+    variant: Option<String>,
+}
+
+impl PuzzleContext {
+    /// The values declared by a puzzle for its real input
+    pub fn from_defaults(defaults: &[(&str, &str)]) -> Self {
+        defaults
+            .iter()
+            .fold(Self::default(), |context, (name, value)| {
+                context.with_param(name, value)
+            })
+    }
+
+    /// Sets a parameter, replacing its previous value
+    pub fn with_param(mut self, name: &str, value: &str) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// The params set, sorted by name
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Sends the messages of the solution to `log`, they are dropped by default
+    pub fn with_log(mut self, log: Log) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// Where the solution reports its messages, progress and artifacts
+    pub fn log(&self) -> &Log {
+        &self.log
+    }
+
+    /// Lets the runner stop the solution through `cancel`
+    pub fn with_cancel(mut self, cancel: CancelToken) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Selects the implementation of the parts named `name`
+    pub fn with_variant(mut self, name: &str) -> Self {
+        self.variant = Some(name.to_string());
+        self
+    }
+
+    /// The selected implementation, `None` for the default one
+    pub fn variant(&self) -> Option<&str> {
+        self.variant.as_deref()
+    }
+
+    /// Fails once the runner gave up on the part, never without a deadline
+    pub fn check_cancelled(&self) -> Result<(), SolveError> {
+        match &self.cancel {
+            Some(cancel) => cancel.check(),
+            None => Ok(()),
+        }
+    }
+
+    /// One `name=value` pair per line, as in the `params.txt` file of an example
+    pub fn parse(text: &str) -> Result<Self, SolveError> {
+        let params = parse_lines(text, |line| {
+            let (name, value) = parse_param(line)?;
+            Ok((name.to_string(), value.to_string()))
+        })?;
+        Ok(Self {
+            params: params.into_iter().collect(),
+            log: Log::default(),
+            cancel: None,
+            variant: None,
+        })
+    }
+
+    /// The params of `overrides` replace the ones already set, its log, token and variant are kept
+    pub fn merge(mut self, overrides: &PuzzleContext) -> Self {
+        self.params.extend(overrides.params.clone());
+        self.log = overrides.log.clone();
+        self.cancel = overrides.cancel.clone();
+        self.variant = overrides.variant.clone();
+        self
+    }
+
+    /// Fails on the params a puzzle does not declare, they are most likely typos
+    pub fn check_names(&self, declared: &[(&str, &str)]) -> Result<(), SolveError> {
+        let unknown = self
+            .params
+            .keys()
+            .find(|name| !declared.iter().any(|(declared, _)| declared == name));
+        match (unknown, declared.is_empty()) {
+            (None, _) => Ok(()),
+            (Some(name), true) => Err(SolveError::new(format!(
+                "unknown parameter {}, this puzzle has no parameter",
+                name
+            ))),
+            (Some(name), false) => Err(SolveError::new(format!(
+                "unknown parameter {}, expected one of {}",
+                name,
+                declared
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    /// Fails on a variant that solves none of the parts, `variants` as given by `Day::variants`
+    pub fn check_variant(&self, variants: &[(&str, u8)]) -> Result<(), SolveError> {
+        match &self.variant {
+            Some(name) if !variants.iter().any(|(variant, _)| variant == name) => {
+                let names = variants.iter().fold(vec![], |mut names, (name, _)| {
+                    if !names.contains(name) {
+                        names.push(*name);
+                    }
+                    names
+                });
+                Err(SolveError::new(format!(
+                    "unknown variant {}, expected one of {}",
+                    name,
+                    names.join(", ")
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The value of a parameter parsed as `T`, fails when it is missing or invalid
+    pub fn param<T: FromStr>(&self, name: &str) -> Result<T, SolveError> {
+        let value = self
+            .params
+            .get(name)
+            .ok_or(SolveError::new(format!("missing parameter {}", name)))?;
+        value
+            .parse::<T>()
+            .map_err(|_| SolveError::new(format!("invalid value {} for parameter {}", value, name)))
+    }
+}
+
+/// Splits a `name=value` parameter, with the spaces around both trimmed
+pub fn parse_param(text: &str) -> Result<(&str, &str), SolveError> {
+    match text.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim(), value.trim())),
+        _ => Err(SolveError::new(format!(
+            "expected a parameter as name=value, got {}",
+            text
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let context = PuzzleContext::parse("row=10\n\nmax_coord = 20\n").unwrap();
+        assert_eq!(
+            context,
+            PuzzleContext::default()
+                .with_param("row", "10")
+                .with_param("max_coord", "20")
+        );
+
+        let error = PuzzleContext::parse("row=10\nmax_coord").unwrap_err();
+        assert_eq!(error.line, Some((2, "max_coord".to_string())));
+    }
+
+    #[test]
+    fn test_param() {
+        let context = PuzzleContext::from_defaults(&[("row", "2000000"), ("max_coord", "4000000")])
+            .merge(&PuzzleContext::default().with_param("row", "10"));
+
+        assert_eq!(context.param::<i32>("row"), Ok(10));
+        assert_eq!(context.param::<i32>("max_coord"), Ok(4_000_000));
+        assert_eq!(
+            context.param::<u8>("max_coord").unwrap_err().to_string(),
+            "invalid value 4000000 for parameter max_coord"
+        );
+        assert_eq!(
+            context.param::<i32>("rocks").unwrap_err().to_string(),
+            "missing parameter rocks"
+        );
+    }
+
+    #[test]
+    fn test_check_names() {
+        let context = PuzzleContext::default().with_param("rows", "10");
+
+        assert!(context.check_names(&[("rows", "2000000")]).is_ok());
+        assert_eq!(
+            context
+                .check_names(&[("row", "2000000"), ("max_coord", "4000000")])
+                .unwrap_err()
+                .to_string(),
+            "unknown parameter rows, expected one of row, max_coord"
+        );
+        assert_eq!(
+            context.check_names(&[]).unwrap_err().to_string(),
+            "unknown parameter rows, this puzzle has no parameter"
+        );
+    }
+
+    #[test]
+    fn test_check_variant() {
+        let variants = [("default", 1), ("default", 2), ("bitmask", 2)];
+        let context = PuzzleContext::default().with_variant("bitmask");
+
+        assert_eq!(context.variant(), Some("bitmask"));
+        assert!(context.check_variant(&variants).is_ok());
+        assert!(PuzzleContext::default().check_variant(&variants).is_ok());
+        assert_eq!(
+            PuzzleContext::default()
+                .with_variant("dp")
+                .check_variant(&variants)
+                .unwrap_err()
+                .to_string(),
+            "unknown variant dp, expected one of default, bitmask"
+        );
+    }
+}
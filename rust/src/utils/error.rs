@@ -0,0 +1,144 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    num::ParseIntError,
+    time::Duration,
+};
+
+/// Why the input could not be parsed or a part could not be solved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveError {
+    /// What went wrong, without the line
+    pub message: String,
+    /// 1-based line number and content of the input line that could not be handled
+    pub line: Option<(usize, String)>,
+    /// The runner stopped waiting for the solution
+    pub timed_out: bool,
+}
+
+impl SolveError {
+    /// An error that is not tied to a line of the input
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: None,
+            timed_out: false,
+        }
+    }
+
+    /// The error of a part that did not finish within `limit`
+    pub fn timeout(limit: Duration) -> Self {
+        Self {
+            timed_out: true,
+            ..Self::new(format!("timed out after {:?}", limit))
+        }
+    }
+
+    /// Attaches the 1-based number and the content of the line that caused the error
+    pub fn at_line(mut self, number: usize, line: &str) -> Self {
+        self.line = Some((number, line.to_string()));
+        self
+    }
+
+    /// Used when the error was raised on a slice of the input starting after `offset` lines
+    pub fn shift_line(mut self, offset: usize) -> Self {
+        if let Some((number, _)) = &mut self.line {
+            *number += offset;
+        }
+        self
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.line {
+            Some((number, line)) => {
+                write!(f, "line {}: {}\n    | {}", number, self.message, line)
+            }
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+impl From<&str> for SolveError {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<String> for SolveError {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<ParseIntError> for SolveError {
+    fn from(error: ParseIntError) -> Self {
+        Self::new(error.to_string())
+    }
+}
+
+/// Parses every non empty line, errors are tagged with the line they come from
+pub fn parse_lines<T, F>(input: &str, parse_line: F) -> Result<Vec<T>, SolveError>
+where
+    F: Fn(&str) -> Result<T, SolveError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| parse_line(line).map_err(|e| e.at_line(i + 1, line)))
+        .collect()
+}
+
+/// Parses blocks separated by an empty line, errors are tagged with their line in the whole input
+pub fn parse_blocks<T, F>(input: &str, parse_block: F) -> Result<Vec<T>, SolveError>
+where
+    F: Fn(&str) -> Result<T, SolveError>,
+{
+    let mut offset = 0;
+    let mut result = vec![];
+    for block in input.split("\n\n") {
+        if !block.trim().is_empty() {
+            let parsed = parse_block(block).map_err(|e| match e.line {
+                Some(_) => e.shift_line(offset),
+                None => e.at_line(offset + 1, block.lines().next().unwrap_or_default()),
+            })?;
+            result.push(parsed);
+        }
+        offset += block.lines().count() + 1;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        let parsed = parse_lines("1\n\n2\n", |l| Ok(l.parse::<u8>()?));
+        assert_eq!(parsed, Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_parse_blocks_error() {
+        let parsed = parse_blocks("1\n2\n\n3\nx\n", |b| {
+            parse_lines(b, |l| Ok(l.parse::<u8>()?))
+        });
+        assert_eq!(parsed.unwrap_err().line, Some((5, "x".to_string())));
+    }
+
+    #[test]
+    fn test_parse_lines_error() {
+        let parsed = parse_lines("1\n2\nx3\n", |l| Ok(l.parse::<u8>()?));
+        let error = parsed.unwrap_err();
+        assert_eq!(error.line, Some((3, "x3".to_string())));
+        assert_eq!(
+            error.to_string(),
+            "line 3: invalid digit found in string\n    | x3"
+        );
+    }
+}
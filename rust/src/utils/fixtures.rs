@@ -0,0 +1,100 @@
+use super::{execute, PuzzleContext, Solution, SolveError};
+
+/// The example inputs of the puzzles, with the answers given in their statement
+pub struct Example {
+    /// The year of the puzzle
+    pub year: u16,
+    /// The day of the puzzle
+    pub day: u8,
+    /// The name of the directory of the example, there can be several per day
+    pub name: &'static str,
+    /// The content of `input.txt`
+    pub input: &'static str,
+    answers: [Option<&'static str>; 2],
+    params: Option<&'static str>,
+}
+
+impl Example {
+    /// The expected answer of a part, `None` when the statement gives none or when the part does
+    /// not exist
+    pub fn answer(&self, puzzle_number: u8) -> Option<&'static str> {
+        let index = (puzzle_number as usize).checked_sub(1)?;
+        self.answers
+            .get(index)
+            .copied()
+            .flatten()
+            .map(|answer| answer.trim_end_matches('\n'))
+    }
+
+    /// The parameters of `params.txt`, the example differs from the real input in them
+    pub fn context(&self) -> Result<PuzzleContext, SolveError> {
+        self.params
+            .map(PuzzleContext::parse)
+            .unwrap_or(Ok(PuzzleContext::default()))
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+/// The examples of a day, sorted by name
+pub fn get_examples(year: u16, day: u8) -> Vec<&'static Example> {
+    EXAMPLES
+        .iter()
+        .filter(|example| example.year == year && example.day == day)
+        .collect()
+}
+
+/// The input of the example `name` of a day, panics when there is none for the tests
+pub fn example_input(year: u16, day: u8, name: &str) -> &'static str {
+    get_examples(year, day)
+        .into_iter()
+        .find(|example| example.name == name)
+        .unwrap_or_else(|| panic!("no example {} for year {} day {}", name, year, day))
+        .input
+}
+
+/// Solves a part of an example and compares the answer with the expected one, for the tests
+pub fn assert_example<S: Solution>(year: u16, day: u8, name: &str, puzzle_number: u8) {
+    let example = get_examples(year, day)
+        .into_iter()
+        .find(|example| example.name == name)
+        .unwrap_or_else(|| panic!("no example {} for year {} day {}", name, year, day));
+    let expected = example.answer(puzzle_number).unwrap_or_else(|| {
+        panic!(
+            "example {} has no answer for puzzle {}",
+            name, puzzle_number
+        )
+    });
+
+    let context = example.context().unwrap();
+    let execution = execute::<S>(example.input, &[puzzle_number], &context);
+    let answer = execution.parts.unwrap().remove(0).answer.unwrap();
+    assert_eq!(answer.to_string(), expected);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples_are_sorted_and_registered() {
+        assert!(!EXAMPLES.is_empty());
+        assert!(EXAMPLES
+            .windows(2)
+            .all(|w| (w[0].year, w[0].day, w[0].name) < (w[1].year, w[1].day, w[1].name)));
+        for example in EXAMPLES {
+            let day = crate::days::get_day(example.year, example.day).unwrap();
+            let context = example.context().unwrap();
+            assert_eq!(context.check_names(day.params), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_answer() {
+        let example = &get_examples(2022, 10)[0];
+        assert_eq!(example.answer(1), Some("13140"));
+        assert_eq!(example.answer(2).unwrap().lines().count(), 6);
+        assert_eq!(example.answer(0), None);
+        assert_eq!(example.answer(3), None);
+    }
+}
@@ -2,12 +2,16 @@ use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssi
 
 use num::{Integer, Signed};
 
+/// A point on an integer grid. The direction of `y` is left to the solutions: it is the row
+/// number in the days that index their input, and points up in 2022 day 9
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub struct Point2<S>
 where
     S: Integer,
 {
+    /// The horizontal coordinate
     pub x: S,
+    /// The vertical coordinate
     pub y: S,
 }
 
@@ -18,12 +22,14 @@ macro_rules! point2 {
 }
 pub(crate) use point2;
 
+/// A displacement between two points, they share their operations
 pub type Vector2<S> = Point2<S>;
 
 impl<S> Point2<S>
 where
     S: Integer + Copy,
 {
+    /// The vector of length 1 along `x`
     pub fn unit_x() -> Self {
         Point2 {
             x: S::one(),
@@ -31,6 +37,7 @@ where
         }
     }
 
+    /// The vector of length 1 along `y`
     pub fn unit_y() -> Self {
         Point2 {
             x: S::zero(),
@@ -43,10 +50,12 @@ impl<S> Point2<S>
 where
     S: Integer + Copy + Signed,
 {
+    /// The Manhattan length of the vector
     pub fn norm_1(&self) -> S {
         self.x.abs() + self.y.abs()
     }
 
+    /// The Manhattan distance between both points
     pub fn distance_1(&self, rhs: &Self) -> S {
         (self.x - rhs.x).abs() + (self.y - rhs.y).abs()
     }
@@ -162,12 +171,15 @@ where
     }
 }
 
+/// A horizontal, vertical or diagonal segment, iterating over it yields both ends
 #[derive(Debug, PartialEq, Clone)]
 pub struct Line2<S>
 where
     S: Integer,
 {
+    /// The first point yielded
     pub start: Point2<S>,
+    /// The last point yielded, included
     pub end: Point2<S>,
 }
 
@@ -175,8 +187,9 @@ impl<S> Line2<S>
 where
     S: Integer + Signed + Copy,
 {
+    /// The Manhattan distance between both ends
     pub fn length_1(&self) -> S {
-        self.end.distance_1(&self.end)
+        self.start.distance_1(&self.end)
     }
 
     fn vec(&self) -> Vector2<S> {
@@ -196,6 +209,7 @@ where
     }
 }
 
+/// The points of a [`Line2`], from its start to its end
 pub struct Line2Iterator<S>
 where
     S: Integer,
@@ -0,0 +1,294 @@
+use std::{
+    fmt::{self, Display},
+    fs::File,
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::{Duration, Instant},
+};
+
+use super::SolveError;
+
+// A progress line is written at most once per interval, and when the work is done
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How much a log lets through, each level includes the ones before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Only the failures
+    Error,
+    /// Also what looks wrong but does not stop the solution
+    Warn,
+    /// Also the progress of the long searches
+    Info,
+    /// Also the intermediate values and artifacts
+    Debug,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Level {
+    type Err = SolveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            _ => Err(SolveError::new(format!(
+                "unknown log level {}, expected error, warn, info or debug",
+                s
+            ))),
+        }
+    }
+}
+
+type Sink = Arc<Mutex<dyn Write + Send>>;
+
+/// Where the solutions write what is neither an answer nor an error, silent by default
+#[derive(Clone, Default)]
+pub struct Log {
+    sink: Option<(Level, Sink)>,
+}
+
+impl Log {
+    /// Writes the messages up to `level` to `sink`
+    pub fn new(level: Level, sink: impl Write + Send + 'static) -> Self {
+        Self {
+            sink: Some((level, Arc::new(Mutex::new(sink)))),
+        }
+    }
+
+    /// Writes the messages up to `level` to the standard error
+    pub fn stderr(level: Level) -> Self {
+        Self::new(level, io::stderr())
+    }
+
+    /// Writes the messages up to `level` to the file at `path`, replacing its content
+    pub fn file(level: Level, path: &Path) -> io::Result<Self> {
+        Ok(Self::new(level, File::create(path)?))
+    }
+
+    /// Whether the messages of `level` are written, to skip computing them otherwise
+    pub fn enabled(&self, level: Level) -> bool {
+        matches!(&self.sink, Some((max, _)) if level <= *max)
+    }
+
+    /// `message` is only formatted when `level` is enabled
+    pub fn message(&self, level: Level, message: impl Display) {
+        if let Some((max, sink)) = &self.sink {
+            if level <= *max {
+                // Logging must never make a solution fail, not even after a part panicked while
+                // writing to the sink
+                let mut sink = sink.lock().unwrap_or_else(PoisonError::into_inner);
+                let _ = writeln!(sink, "[{}] {}", level, message);
+            }
+        }
+    }
+
+    /// Writes `message` at the info level
+    pub fn info(&self, message: impl Display) {
+        self.message(Level::Info, message);
+    }
+
+    /// Writes `message` at the debug level
+    pub fn debug(&self, message: impl Display) {
+        self.message(Level::Debug, message);
+    }
+
+    /// A multiline value like a grid, written below a header with its name
+    pub fn artifact(&self, name: &str, content: impl Display) {
+        self.debug(format_args!("{}:\n{}", name, content));
+    }
+
+    /// Starts counting the `total` items of a step named `label`
+    pub fn progress(&self, label: &str, total: usize) -> Progress<'_> {
+        let start = Instant::now();
+        Progress {
+            log: self,
+            label: label.to_string(),
+            total,
+            done: AtomicUsize::new(0),
+            start,
+            last_report: Mutex::new(start),
+        }
+    }
+}
+
+impl fmt::Debug for Log {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.sink {
+            Some((level, _)) => write!(f, "Log({})", level),
+            None => write!(f, "Log(silent)"),
+        }
+    }
+}
+
+// Two logs are the same when they write to the same sink
+impl PartialEq for Log {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.sink, &other.sink) {
+            (None, None) => true,
+            (Some((level, sink)), Some((other_level, other_sink))) => {
+                level == other_level && Arc::ptr_eq(sink, other_sink)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Log {}
+
+/// Counts the items handled out of `total` and reports them with an estimate of the remaining time,
+/// `tick` takes `&self` so that parallel iterators can share it
+pub struct Progress<'a> {
+    log: &'a Log,
+    label: String,
+    total: usize,
+    done: AtomicUsize,
+    start: Instant,
+    last_report: Mutex<Instant>,
+}
+
+impl Progress<'_> {
+    /// Counts one more item, and reports the progress at the info level when it is due
+    pub fn tick(&self) {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        if !self.log.enabled(Level::Info) {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut last_report = self
+            .last_report
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if done < self.total && now - *last_report < PROGRESS_INTERVAL {
+            return;
+        }
+        *last_report = now;
+
+        let elapsed = now - self.start;
+        let remaining = elapsed.mul_f64(self.total.saturating_sub(done) as f64 / done as f64);
+        self.log.info(format_args!(
+            "{}: {}/{} ({}%), eta {:.1}s",
+            self.label,
+            done,
+            self.total,
+            done * 100 / self.total.max(1),
+            remaining.as_secs_f64()
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_levels() {
+        let buffer = Buffer::default();
+        let log = Log::new(Level::Info, buffer.clone());
+
+        log.info("starting");
+        log.debug("hidden");
+        log.message(Level::Warn, format_args!("{} cycles", 3));
+        assert!(log.enabled(Level::Error));
+        assert!(!log.enabled(Level::Debug));
+        assert_eq!(buffer.text(), "[info] starting\n[warn] 3 cycles\n");
+
+        assert!(!Log::default().enabled(Level::Error));
+        assert_eq!("debug".parse::<Level>(), Ok(Level::Debug));
+        assert!("verbose".parse::<Level>().is_err());
+    }
+
+    // Panics on its first write, like a part interrupted while logging
+    struct Flaky(bool, Buffer);
+
+    impl Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if !self.0 {
+                self.0 = true;
+                panic!("interrupted");
+            }
+            self.1.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_poisoned_sink() {
+        let buffer = Buffer::default();
+        let log = Log::new(Level::Info, Flaky(false, buffer.clone()));
+
+        let logged = std::panic::catch_unwind(|| log.info("lost"));
+        assert!(logged.is_err());
+        log.info("kept");
+        assert_eq!(
+            buffer.text(),
+            "[info] kept
+"
+        );
+    }
+
+    #[test]
+    fn test_artifact() {
+        let buffer = Buffer::default();
+        let log = Log::new(Level::Debug, buffer.clone());
+
+        log.artifact("grid", "#.\n.#");
+        assert_eq!(buffer.text(), "[debug] grid:\n#.\n.#\n");
+    }
+
+    #[test]
+    fn test_progress() {
+        let buffer = Buffer::default();
+        let log = Log::new(Level::Info, buffer.clone());
+
+        let progress = log.progress("machines", 3);
+        (0..3).for_each(|_| progress.tick());
+        // The intermediate ticks are too close to be reported
+        assert_eq!(buffer.text(), "[info] machines: 3/3 (100%), eta 0.0s\n");
+
+        let silent = Log::default();
+        let progress = silent.progress("machines", 3);
+        progress.tick();
+    }
+}
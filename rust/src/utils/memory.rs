@@ -0,0 +1,170 @@
+use std::fmt::{self, Display};
+
+/// What a step allocated, counted by the global allocator of the `alloc-stats` feature
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations and reallocations
+    pub allocations: u64,
+    /// Total size of the allocations, including the freed ones
+    pub bytes: u64,
+    /// Highest amount of live bytes above the ones live when the step started
+    pub peak: u64,
+}
+
+/// A size in bytes with the largest decimal unit below it, like `1.50 MB`
+pub fn format_bytes(bytes: u64) -> String {
+    let bytes = bytes as f64;
+    match bytes {
+        b if b >= 1e9 => format!("{:.2} GB", b / 1e9),
+        b if b >= 1e6 => format!("{:.2} MB", b / 1e6),
+        b if b >= 1e3 => format!("{:.2} kB", b / 1e3),
+        b => format!("{} B", b),
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {}, peak {}",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    #[derive(Clone, Copy)]
+    pub struct Counters {
+        pub allocations: u64,
+        pub bytes: u64,
+        pub live: i64,
+        pub peak: i64,
+    }
+
+    // Per thread so that the days running in parallel do not count each other's allocations,
+    // the allocations of the threads a solution starts itself, like rayon's, are not counted
+    thread_local! {
+        static COUNTERS: Cell<Counters> = const {
+            Cell::new(Counters {
+                allocations: 0,
+                bytes: 0,
+                live: 0,
+                peak: 0,
+            })
+        };
+    }
+
+    pub fn update(update: impl FnOnce(&mut Counters)) -> Option<Counters> {
+        COUNTERS
+            .try_with(|counters| {
+                let mut value = counters.get();
+                update(&mut value);
+                value.peak = value.peak.max(value.live);
+                counters.set(value);
+                value
+            })
+            .ok()
+    }
+
+    pub struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            update(|counters| {
+                counters.allocations += 1;
+                counters.bytes += layout.size() as u64;
+                counters.live += layout.size() as i64;
+            });
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            update(|counters| counters.live -= layout.size() as i64);
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            update(|counters| {
+                counters.allocations += 1;
+                counters.bytes += new_size as u64;
+                counters.live += new_size as i64 - layout.size() as i64;
+            });
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+}
+
+/// Runs `step` and returns what it allocated on this thread, `None` without the `alloc-stats` feature
+#[cfg(feature = "alloc-stats")]
+pub fn measure<T>(step: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    let before = counting::update(|counters| counters.peak = counters.live);
+    let result = step();
+    let after = counting::update(|_| {});
+
+    let stats = before.zip(after).map(|(before, after)| AllocStats {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+        peak: (after.peak - before.live).max(0) as u64,
+    });
+    (result, stats)
+}
+
+/// Runs `step`, nothing is counted without the `alloc-stats` feature
+#[cfg(not(feature = "alloc-stats"))]
+pub fn measure<T>(step: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    (step(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1_500_000), "1.50 MB");
+        let stats = AllocStats {
+            allocations: 3,
+            bytes: 2048,
+            peak: 1024,
+        };
+        assert_eq!(stats.to_string(), "3 allocations, 2.05 kB, peak 1.02 kB");
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn test_measure() {
+        let (sum, stats) = measure(|| {
+            let kept = vec![0u8; 1000];
+            let dropped = vec![1u8; 3000];
+            drop(dropped);
+            kept.len()
+        });
+        assert_eq!(sum, 1000);
+        assert_eq!(
+            stats,
+            Some(AllocStats {
+                allocations: 2,
+                bytes: 4000,
+                peak: 4000,
+            })
+        );
+    }
+
+    #[cfg(not(feature = "alloc-stats"))]
+    #[test]
+    fn test_measure() {
+        assert_eq!(measure(|| 1), (1, None));
+    }
+}
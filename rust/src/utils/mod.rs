@@ -1,4 +1,29 @@
+//! What the solutions share, re-exported at the root of the module except `geometry`, and what
+//! runs them: `bench` to measure them, `memory` to count their allocations and `fixtures` for the
+//! examples of the statements
+
+/// Repeated measures of the parsing and the parts
+pub mod bench;
+/// Stopping the parts that run past their deadline
+pub mod cancel;
+/// The parameters, log, variant and cancellation given to the parts
+pub mod context;
+/// The errors of the parsing and the parts
+pub mod error;
+/// The examples of the statements with their answers
+pub mod fixtures;
+/// Points, vectors and lines on integer grids
 pub mod geometry;
+/// The messages, progress and artifacts of the solutions
+pub mod log;
+/// Counting the allocations of a step
+pub mod memory;
+/// The `Solution` trait, the answers and the runners
+#[allow(clippy::module_inception)]
 pub mod utils;
 
+pub use cancel::*;
+pub use context::*;
+pub use error::*;
+pub use log::*;
 pub use utils::*;
@@ -1,22 +1,546 @@
-#[allow(dead_code)]
-pub enum InputKind {
-    Test,
-    Run,
+use std::{
+    any::Any,
+    fmt::{self, Display},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::{
+    bench::{BenchConfig, DayBench},
+    memory::{self, AllocStats},
+    CancelToken, PuzzleContext, SolveError,
+};
+
+/// What a part returns, compared with the recorded answers through its `Display`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    /// Most answers, compared numerically with the recorded ones
+    Integer(i128),
+    /// A word or a code
+    Text(String),
+    /// Rows of lit pixels, for the puzzles answered by reading letters on a screen
+    Grid(Vec<Vec<bool>>),
+}
+
+impl Answer {
+    /// Grids do not fit in a table cell and are printed below it
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Grid(_))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Grid(rows) => {
+                let rows = rows
+                    .iter()
+                    .map(|row| row.iter().map(|&p| if p { '#' } else { '.' }).collect())
+                    .collect::<Vec<String>>();
+                write!(f, "{}", rows.join("\n"))
+            }
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Integer(n as i128)
+            }
+        }
+
+        impl From<&$t> for Answer {
+            fn from(n: &$t) -> Self {
+                Answer::Integer(*n as i128)
+            }
+        })*
+    };
+}
+answer_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// A day parses its input once, both parts then work on the parsed value. The solutions are
+/// unit structs, `'static` lets them list their variants in a constant
+pub trait Solution: 'static {
+    /// The input once parsed, shared by both parts
+    type Parsed;
+
+    /// The parameters read from the context by the parts, with their value for the real input
+    const PARAMS: &'static [(&'static str, &'static str)] = &[];
+    /// Other implementations of the parts, run instead of `part_1` and `part_2` when selected
+    const VARIANTS: &'static [Variant<Self>] = &[];
+
+    /// Parses the raw input, errors point at the line that could not be read
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError>;
+    /// Solves the first part
+    fn part_1(input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError>;
+    /// Solves the second part
+    fn part_2(input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError>;
+}
+
+/// The name of the `part_1` and `part_2` implementations among the variants
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// A named implementation of one part, selected with `PuzzleContext::with_variant`
+pub struct Variant<S: Solution + ?Sized> {
+    /// The name given to `--variant`
+    pub name: &'static str,
+    /// The part it solves, 1 or 2
+    pub puzzle_number: u8,
+    /// Solves the part like `part_1` or `part_2` would
+    pub solve: fn(&S::Parsed, &PuzzleContext) -> Result<Answer, SolveError>,
+}
+
+/// The names of the variants of `S` with the part they solve, the default ones first
+pub fn variant_names<S: Solution>() -> Vec<(&'static str, u8)> {
+    [(DEFAULT_VARIANT, 1), (DEFAULT_VARIANT, 2)]
+        .into_iter()
+        .chain(S::VARIANTS.iter().map(|v| (v.name, v.puzzle_number)))
+        .collect()
+}
+
+/// Solves a part with the variant selected in `context`, or with `part_1` and `part_2` when
+/// none is selected or when the selected one does not solve this part
+pub fn solve<S: Solution>(
+    parsed: &S::Parsed,
+    puzzle_number: u8,
+    context: &PuzzleContext,
+) -> Result<Answer, SolveError> {
+    let variant = S::VARIANTS
+        .iter()
+        .find(|v| v.puzzle_number == puzzle_number && Some(v.name) == context.variant());
+    match (variant, puzzle_number) {
+        (Some(variant), _) => (variant.solve)(parsed, context),
+        (None, 1) => S::part_1(parsed, context),
+        (None, 2) => S::part_2(parsed, context),
+        _ => Err(SolveError::new(format!(
+            "unknown puzzle number {}",
+            puzzle_number
+        ))),
+    }
+}
+
+/// The answer of one part with the time and memory it took
+pub struct PartResult {
+    /// 1 or 2
+    pub puzzle_number: u8,
+    /// The answer, or why the part failed, panicked or timed out
+    pub answer: Result<Answer, SolveError>,
+    /// Time spent solving, the timeout when the part was given up on
+    pub time: Duration,
+    /// What solving allocated, with the `alloc-stats` feature
+    pub alloc: Option<AllocStats>,
+}
+
+/// Everything a day reports after solving the requested parts
+pub struct Execution {
+    /// Time spent parsing the input
+    pub parse_time: Duration,
+    /// What parsing allocated, with the `alloc-stats` feature
+    pub parse_alloc: Option<AllocStats>,
+    /// Fails as a whole when the input cannot be parsed
+    pub parts: Result<Vec<PartResult>, SolveError>,
+}
+
+impl Execution {
+    /// Whether the parsing or one of the parts failed
+    pub fn has_failures(&self) -> bool {
+        match &self.parts {
+            Ok(parts) => parts.iter().any(|part| part.answer.is_err()),
+            Err(_) => true,
+        }
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+// A panic in a solution is reported as its failure, the other parts and days still run
+fn catch_panic<T>(solve: impl FnOnce() -> Result<T, SolveError>) -> Result<T, SolveError> {
+    panic::catch_unwind(AssertUnwindSafe(solve)).unwrap_or_else(|payload| {
+        Err(SolveError::new(format!(
+            "panicked: {}",
+            panic_message(payload)
+        )))
+    })
+}
+
+/// Parses `input` and solves the parts in `puzzle_numbers` in order, panics are reported as
+/// failures and the params of `context` override the defaults declared in `S::PARAMS`
+pub fn execute<S: Solution>(
+    input: &str,
+    puzzle_numbers: &[u8],
+    context: &PuzzleContext,
+) -> Execution {
+    let start = Instant::now();
+    let (parsed, parse_alloc) = memory::measure(|| catch_panic(|| S::parse(input)));
+    let parse_time = start.elapsed();
+
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            return Execution {
+                parse_time,
+                parse_alloc,
+                parts: Err(e),
+            }
+        }
+    };
+
+    let context = PuzzleContext::from_defaults(S::PARAMS).merge(context);
+    let parts = puzzle_numbers
+        .iter()
+        .map(|&puzzle_number| solve_part::<S>(&parsed, puzzle_number, &context))
+        .collect();
+
+    Execution {
+        parse_time,
+        parse_alloc,
+        parts: Ok(parts),
+    }
+}
+
+fn solve_part<S: Solution>(
+    parsed: &S::Parsed,
+    puzzle_number: u8,
+    context: &PuzzleContext,
+) -> PartResult {
+    let start = Instant::now();
+    let (answer, alloc) =
+        memory::measure(|| catch_panic(|| solve::<S>(parsed, puzzle_number, context)));
+    PartResult {
+        puzzle_number,
+        answer,
+        time: start.elapsed(),
+        alloc,
+    }
+}
+
+// What the worker thread of `execute_with_timeout` tells the runner
+enum Event {
+    Parsed(Duration, Option<AllocStats>, Result<(), SolveError>),
+    Started(u8, CancelToken),
+    Solved(PartResult),
+}
+
+// The worker threads of `execute_with_timeout` that have not returned yet
+static WORKERS: AtomicUsize = AtomicUsize::new(0);
+
+// Counts its worker as alive until the thread returns, even through a panic
+struct WorkerGuard;
+
+impl WorkerGuard {
+    fn new() -> Self {
+        WORKERS.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for WorkerGuard {
+    fn drop(&mut self) {
+        WORKERS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// The number of worker threads of `execute_with_timeout` still running, including the ones
+/// left behind by parts that did not stop after their timeout
+pub fn alive_workers() -> usize {
+    WORKERS.load(Ordering::SeqCst)
 }
 
+/// Like `execute`, on a worker thread: the parsing and each part get `timeout` to finish from
+/// when they start. A part that exceeds it is reported as timed out and its token is cancelled.
+/// The next part waits up to `timeout` for the worker to come back from the cancelled one,
+/// when the part does not poll the token the worker is left behind and the remaining parts are
+/// reported as skipped
+pub fn execute_with_timeout<S: Solution>(
+    input: &str,
+    puzzle_numbers: &[u8],
+    context: &PuzzleContext,
+    timeout: Duration,
+) -> Execution {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_string();
+    let numbers = puzzle_numbers.to_vec();
+    let context = PuzzleContext::from_defaults(S::PARAMS).merge(context);
+    let guard = WorkerGuard::new();
+    thread::spawn(move || {
+        let _guard = guard;
+        let start = Instant::now();
+        let (parsed, alloc) = memory::measure(|| catch_panic(|| S::parse(&input)));
+        let parsed = match parsed {
+            Ok(parsed) => {
+                let _ = sender.send(Event::Parsed(start.elapsed(), alloc, Ok(())));
+                parsed
+            }
+            Err(e) => {
+                let _ = sender.send(Event::Parsed(start.elapsed(), alloc, Err(e)));
+                return;
+            }
+        };
+        for puzzle_number in numbers {
+            let cancel = CancelToken::default();
+            // The runner is gone, nobody is waiting for the other parts
+            if sender
+                .send(Event::Started(puzzle_number, cancel.clone()))
+                .is_err()
+            {
+                return;
+            }
+            let context = context.clone().with_cancel(cancel);
+            let _ = sender.send(Event::Solved(solve_part::<S>(
+                &parsed,
+                puzzle_number,
+                &context,
+            )));
+        }
+    });
+
+    let (parse_time, parse_alloc) = match receiver.recv_timeout(timeout) {
+        Ok(Event::Parsed(parse_time, parse_alloc, Ok(()))) => (parse_time, parse_alloc),
+        Ok(Event::Parsed(parse_time, parse_alloc, Err(e))) => {
+            return Execution {
+                parse_time,
+                parse_alloc,
+                parts: Err(e),
+            }
+        }
+        _ => {
+            return Execution {
+                parse_time: timeout,
+                parse_alloc: None,
+                parts: Err(SolveError::timeout(timeout)),
+            }
+        }
+    };
+
+    // The last part that timed out, and the one the worker never came back from
+    let mut timed_out = None;
+    let mut stuck = None;
+    let parts = puzzle_numbers
+        .iter()
+        .map(|&puzzle_number| {
+            let failed = |error, time| PartResult {
+                puzzle_number,
+                answer: Err(error),
+                time,
+                alloc: None,
+            };
+            let skipped = |stuck| {
+                let error = SolveError::new(format!("skipped, part {} did not stop", stuck));
+                failed(error, Duration::ZERO)
+            };
+            if let Some(stuck) = stuck {
+                return skipped(stuck);
+            }
+
+            // Until the part starts, this is the grace given to the previous one to stop
+            let mut deadline = Instant::now() + timeout;
+            let mut running = None;
+            loop {
+                let event =
+                    receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()));
+                let error = match event {
+                    Ok(Event::Started(number, cancel)) if number == puzzle_number => {
+                        deadline = Instant::now() + timeout;
+                        running = Some(cancel);
+                        continue;
+                    }
+                    Ok(Event::Solved(part)) if part.puzzle_number == puzzle_number => return part,
+                    // The late answer of a part that already timed out
+                    Ok(_) => continue,
+                    Err(RecvTimeoutError::Timeout) => match (&running, timed_out) {
+                        (None, Some(previous)) => {
+                            stuck = Some(previous);
+                            return skipped(previous);
+                        }
+                        _ => SolveError::timeout(timeout),
+                    },
+                    Err(RecvTimeoutError::Disconnected) => {
+                        SolveError::new("the worker thread stopped")
+                    }
+                };
+                if let Some(cancel) = running {
+                    cancel.cancel();
+                }
+                timed_out = Some(puzzle_number);
+                return failed(error, timeout);
+            }
+        })
+        .collect();
+
+    Execution {
+        parse_time,
+        parse_alloc,
+        parts: Ok(parts),
+    }
+}
+
+/// A registered day, the functions are `execute`, `execute_with_timeout` and `bench` for its solution
 pub struct Day {
+    /// The year of the puzzle
+    pub year: u16,
+    /// The day of the puzzle, from 1 to 25
     pub day: u8,
-    pub puzzle_1: fn(&str) -> String,
-    pub puzzle_2: fn(&str) -> String,
+    /// See [`execute`]
+    pub execute: fn(&str, &[u8], &PuzzleContext) -> Execution,
+    /// See [`execute_with_timeout`]
+    pub execute_with_timeout: fn(&str, &[u8], &PuzzleContext, Duration) -> Execution,
+    /// See [`bench`](super::bench::bench)
+    pub bench: fn(&str, &Execution, &BenchConfig, &PuzzleContext) -> Result<DayBench, SolveError>,
+    /// The parameters of the solution with their value for the real input
+    pub params: &'static [(&'static str, &'static str)],
+    /// See [`variant_names`]
+    pub variants: fn() -> Vec<(&'static str, u8)>,
 }
 
 macro_rules! get_day {
-    ($n: tt, $mod:tt) => {
+    ($year: tt, $n: tt, $($mod:ident)::+) => {
         Day {
+            year: $year,
             day: $n,
-            puzzle_1: $mod::puzzle_1,
-            puzzle_2: $mod::puzzle_2,
+            execute: crate::utils::execute::<$($mod::)+Puzzle>,
+            execute_with_timeout: crate::utils::execute_with_timeout::<$($mod::)+Puzzle>,
+            bench: crate::utils::bench::bench::<$($mod::)+Puzzle>,
+            params: <$($mod::)+Puzzle as crate::utils::Solution>::PARAMS,
+            variants: crate::utils::variant_names::<$($mod::)+Puzzle>,
         }
     };
 }
 pub(crate) use get_day;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The first part only stops when cancelled
+    struct Stuck;
+
+    impl Solution for Stuck {
+        type Parsed = u32;
+
+        fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+            Ok(input.trim().parse()?)
+        }
+
+        fn part_1(_input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError> {
+            loop {
+                context.check_cancelled()?;
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+
+        fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+            Ok((input * 2).into())
+        }
+    }
+
+    // The first part ignores the cancellation and runs past the grace of the second one
+    struct Deaf;
+
+    impl Solution for Deaf {
+        type Parsed = ();
+
+        fn parse(_input: &str) -> Result<Self::Parsed, SolveError> {
+            Ok(())
+        }
+
+        fn part_1(_input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+            thread::sleep(Duration::from_millis(500));
+            Ok(1.into())
+        }
+
+        fn part_2(_input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+            Ok(2.into())
+        }
+    }
+
+    struct Panicking;
+
+    impl Solution for Panicking {
+        type Parsed = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+            Ok(input.lines().map(|line| line.parse().unwrap()).collect())
+        }
+
+        fn part_1(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+            panic!("no line {}", input.len() + 1)
+        }
+
+        fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+            Ok(input.iter().sum::<u32>().into())
+        }
+    }
+
+    #[test]
+    fn test_execute_catches_panics() {
+        let execution = execute::<Panicking>("1\n2", &[1, 2], &PuzzleContext::default());
+        let parts = execution.parts.unwrap();
+        assert_eq!(
+            parts[0].answer.as_ref().unwrap_err().to_string(),
+            "panicked: no line 3"
+        );
+        assert_eq!(parts[1].answer, Ok(Answer::Integer(3)));
+
+        let execution = execute::<Panicking>("x", &[2], &PuzzleContext::default());
+        assert!(matches!(execution.parts, Err(e) if e.message.starts_with("panicked: ")));
+    }
+
+    #[test]
+    fn test_execute_with_timeout() {
+        let timeout = Duration::from_millis(50);
+        let execution =
+            execute_with_timeout::<Stuck>("21", &[1, 2], &PuzzleContext::default(), timeout);
+
+        let parts = execution.parts.unwrap();
+        let error = parts[0].answer.as_ref().unwrap_err();
+        assert!(error.timed_out);
+        assert_eq!(error.to_string(), "timed out after 50ms");
+        assert_eq!(parts[1].answer, Ok(Answer::Integer(42)));
+
+        let execution =
+            execute_with_timeout::<Stuck>("x", &[2], &PuzzleContext::default(), timeout);
+        assert!(matches!(execution.parts, Err(e) if !e.timed_out));
+    }
+
+    #[test]
+    fn test_execute_with_timeout_skips_after_stuck_part() {
+        let timeout = Duration::from_millis(50);
+        let execution =
+            execute_with_timeout::<Deaf>("", &[1, 2], &PuzzleContext::default(), timeout);
+
+        let parts = execution.parts.unwrap();
+        assert!(parts[0].answer.as_ref().unwrap_err().timed_out);
+        let error = parts[1].answer.as_ref().unwrap_err();
+        assert!(!error.timed_out);
+        assert_eq!(error.to_string(), "skipped, part 1 did not stop");
+    }
+}
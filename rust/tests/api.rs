@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use avc::{
+    days::get_day,
+    utils::{
+        bench::{bench, BenchConfig},
+        execute,
+        fixtures::EXAMPLES,
+        geometry::{Line2, Point2},
+        variant_names, Answer, PuzzleContext, Solution, SolveError, Variant,
+    },
+};
+
+#[test]
+fn test_every_variant_on_the_examples() {
+    for example in EXAMPLES {
+        let day = get_day(example.year, example.day).unwrap();
+        // Every variant of a part must find the answer of the statement
+        for (variant, puzzle_number) in (day.variants)() {
+            let Some(expected) = example.answer(puzzle_number) else {
+                continue;
+            };
+            let context = example.context().unwrap().with_variant(variant);
+            let execution = (day.execute)(example.input, &[puzzle_number], &context);
+            let answer = execution.parts.unwrap().remove(0).answer.unwrap();
+            assert_eq!(
+                answer.to_string(),
+                expected,
+                "{} day {} example {} part {} variant {}",
+                example.year,
+                example.day,
+                example.name,
+                puzzle_number,
+                variant
+            );
+        }
+    }
+}
+
+// A solution written outside of the crate, with a parameter and a variant
+struct Sum;
+
+fn max_sorted(input: &[i64]) -> Result<Answer, SolveError> {
+    let mut sorted = input.to_vec();
+    sorted.sort();
+    sorted
+        .last()
+        .copied()
+        .map(Answer::from)
+        .ok_or_else(|| SolveError::new("empty input"))
+}
+
+impl Solution for Sum {
+    type Parsed = Vec<i64>;
+
+    const PARAMS: &'static [(&'static str, &'static str)] = &[("factor", "1")];
+    const VARIANTS: &'static [Variant<Self>] = &[Variant {
+        name: "sorted",
+        puzzle_number: 2,
+        solve: |input, _context| max_sorted(input),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        avc::utils::parse_lines(input, |line| Ok(line.parse()?))
+    }
+
+    fn part_1(input: &Self::Parsed, context: &PuzzleContext) -> Result<Answer, SolveError> {
+        let factor: i64 = context.param("factor")?;
+        Ok((input.iter().sum::<i64>() * factor).into())
+    }
+
+    fn part_2(input: &Self::Parsed, _context: &PuzzleContext) -> Result<Answer, SolveError> {
+        input
+            .iter()
+            .max()
+            .map(Answer::from)
+            .ok_or_else(|| SolveError::new("empty input"))
+    }
+}
+
+#[test]
+fn test_execute_external_solution() {
+    let context = PuzzleContext::default().with_param("factor", "3");
+    let execution = execute::<Sum>("1\n5\n2\n", &[1, 2], &context);
+    let answers = execution
+        .parts
+        .unwrap()
+        .into_iter()
+        .map(|part| part.answer)
+        .collect::<Vec<_>>();
+    assert_eq!(answers, [Ok(Answer::Integer(24)), Ok(Answer::Integer(5))]);
+
+    let execution = execute::<Sum>("1\nx\n", &[1], &PuzzleContext::default());
+    let error = execution.parts.err().unwrap();
+    assert_eq!(error.line.map(|(number, _)| number), Some(2));
+}
+
+#[test]
+fn test_bench_external_variant() {
+    assert_eq!(
+        variant_names::<Sum>(),
+        [("default", 1), ("default", 2), ("sorted", 2)]
+    );
+
+    let context = PuzzleContext::default().with_variant("sorted");
+    let execution = execute::<Sum>("1\n5\n2\n", &[2], &context);
+    let config = BenchConfig {
+        warmup: Duration::ZERO,
+        budget: Duration::from_secs(60),
+        max_runs: 1,
+    };
+    let measured = bench::<Sum>("1\n5\n2\n", &execution, &config, &context).unwrap();
+    assert_eq!(measured.parse.runs, 1);
+    assert_eq!(measured.parts.len(), 1);
+    assert_eq!(measured.parts[0].puzzle_number, 2);
+    assert_eq!(measured.parts[0].stats.as_ref().unwrap().runs, 1);
+}
+
+#[test]
+fn test_geometry() {
+    let line = Line2 {
+        start: Point2 { x: 0, y: 0 },
+        end: Point2 { x: 2, y: -2 },
+    };
+    assert_eq!(line.length_1(), 4);
+    let points = line.into_iter().collect::<Vec<_>>();
+    assert_eq!(
+        points,
+        [
+            Point2 { x: 0, y: 0 },
+            Point2 { x: 1, y: -1 },
+            Point2 { x: 2, y: -2 }
+        ]
+    );
+    assert_eq!(points[2].distance_1(&Point2::unit_x()), 3);
+}
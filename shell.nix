@@ -5,6 +5,7 @@ in pkgs.mkShell {
 
   buildInputs = with pkgs; [
     cargo
+    curl
     clippy
     docopts
     rust-analyzer